@@ -238,7 +238,12 @@ app.get('/crates/:name/:version/function', async (c) => {
     )
   }
 
+  // Accept paths as written in Rust: `foo::bar`, `crate::foo::bar` or
+  // `crate_name::foo::bar`
   const segments = path.split('::')
+  if (segments[0] === 'crate' || segments[0] === name.replace(/-/g, '_')) {
+    segments.shift()
+  }
   const pathToFunction = segments.join('/')
 
   try {
@@ -246,7 +251,25 @@ app.get('/crates/:name/:version/function', async (c) => {
     const r2Key = `crates/${name}/${version}/${pathToFunction}.json`
 
     // Retrieve the item's JSON file from R2
-    const itemObj = await c.env.CRATES_STORAGE.get(r2Key)
+    let itemObj = await c.env.CRATES_STORAGE.get(r2Key)
+
+    if (!itemObj) {
+      // Items are stored where they're defined; the path may be a re-export
+      const publicPathsObj = await c.env.CRATES_STORAGE.get(
+        `crates/${name}/${version}/public_paths.json`,
+      )
+      const publicPaths: Record<string, string[]> = publicPathsObj
+        ? await publicPathsObj.json()
+        : {}
+      const definingPath = Object.keys(publicPaths).find((key) =>
+        publicPaths[key].includes(segments.join('::')),
+      )
+      if (definingPath) {
+        itemObj = await c.env.CRATES_STORAGE.get(
+          `crates/${name}/${version}/${definingPath.split('::').join('/')}.json`,
+        )
+      }
+    }
 
     if (!itemObj) {
       return c.json(
@@ -266,6 +289,13 @@ app.get('/crates/:name/:version/function', async (c) => {
       success: true,
       data: {
         signature: functionDetails.signature,
+        receiver: functionDetails.receiver,
+        params: functionDetails.params,
+        return_type: functionDetails.returnType,
+        generics: functionDetails.generics,
+        errors: itemData.docSections?.errors ?? null,
+        panics: itemData.docSections?.panics ?? null,
+        safety: itemData.docSections?.safety ?? null,
         agent_summary: itemData.agent_summary || 'No summary available',
       },
     })
@@ -28,9 +28,50 @@ export class TarExtractor {
 
     // Then extract tar entries
     const files: Map<string, string> = new Map()
-    let offset = 0
     let folderPrefix
 
+    for (const { filePath, fileData } of this.readEntries(tarData)) {
+      if (!folderPrefix) {
+        // Extract folder prefix if it exists
+        folderPrefix = filePath.substring(0, filePath.indexOf('/src/'))
+      }
+
+      // Only process .rs files
+      if (filePath.endsWith('.rs')) {
+        const content = new TextDecoder().decode(fileData)
+
+        files.set(filePath.replace(folderPrefix + '/', ''), content)
+      }
+    }
+
+    return files
+  }
+
+  // crates.io tarballs hold a single `name-version/` folder, with the
+  // manifest at its top level
+  static async extractManifest(
+    tarGzData: Uint8Array,
+  ): Promise<string | undefined> {
+    const tarData = inflate(tarGzData)
+
+    for (const { filePath, fileData } of this.readEntries(tarData)) {
+      if (
+        filePath.split('/').length === 2 &&
+        filePath.endsWith('/Cargo.toml')
+      ) {
+        return new TextDecoder().decode(fileData)
+      }
+    }
+
+    return undefined
+  }
+
+  // Yields the regular files of an uncompressed tar archive
+  private static *readEntries(
+    tarData: Uint8Array,
+  ): Generator<{ filePath: string; fileData: Uint8Array }> {
+    let offset = 0
+
     while (offset < tarData.length) {
       const header = this.parseTarHeader(tarData, offset)
 
@@ -43,19 +84,9 @@ export class TarExtractor {
 
       if (header.typeflag === '0' || header.typeflag === '') {
         // Regular file
-        const filePath = header.name
-
-        if (!folderPrefix) {
-          // Extract folder prefix if it exists
-          folderPrefix = filePath.substring(0, filePath.indexOf('/src/'))
-        }
-
-        // Only process .rs files
-        if (filePath.endsWith('.rs')) {
-          const fileData = tarData.slice(offset, offset + header.size)
-          const content = new TextDecoder().decode(fileData)
-
-          files.set(filePath.replace(folderPrefix + '/', ''), content)
+        yield {
+          filePath: header.name,
+          fileData: tarData.slice(offset, offset + header.size),
         }
       }
 
@@ -63,8 +94,6 @@ export class TarExtractor {
       const paddedSize = Math.ceil(header.size / 512) * 512
       offset += paddedSize
     }
-
-    return files
   }
 
   private static parseTarHeader(data: Uint8Array, offset: number): TarHeader {
@@ -10,6 +10,7 @@ import {
   CrateStatus,
   type QueueMessage,
   type CrateWithData,
+  type Edition,
   type ParseResponse,
 } from '@riddick/types'
 import { TarExtractor } from './extractor'
@@ -18,6 +19,7 @@ import {
   getPromptForItem,
   getSystemPrompt,
 } from './prompts'
+import { resolvePublicPaths } from './reexports'
 import { NonRetryableError } from 'cloudflare:workflows'
 
 interface RustParser extends Fetcher {
@@ -25,6 +27,9 @@ interface RustParser extends Fetcher {
     code: string
     filePath?: string | null
     includePrivate?: boolean
+    cfg?: string[] | null
+    edition?: Edition | null
+    manifest?: string | null
   }): Promise<ParseResponse>
 }
 
@@ -91,11 +96,16 @@ export class CrateProcessor {
 
     // Extract and read Rust files
     const files = await this.extractRustFiles(new Uint8Array(tarballBuffer))
+    // The manifest tells the parser which edition the crate is written in
+    const manifest = await TarExtractor.extractManifest(
+      new Uint8Array(tarballBuffer),
+    )
 
     return {
       name: crateName,
       version,
       files,
+      manifest,
     }
   }
 
@@ -110,8 +120,10 @@ export class CrateProcessor {
     crateName: string,
     version: string,
     files: Map<string, string>,
+    manifest?: string,
   ): Promise<StoredModule> {
     const processedModules = new Set<string>()
+    const parsedModules = new Map<string, FileInfo>()
 
     // Start with lib.rs or main.rs as entrypoint
     const entrypoint = 'src/lib.rs'
@@ -127,7 +139,7 @@ export class CrateProcessor {
 
     const modulePath: string[] = []
 
-    return await this.parseAndStoreFile(
+    const storedItem = await this.parseAndStoreFile(
       crateId,
       crateName,
       version,
@@ -135,7 +147,26 @@ export class CrateProcessor {
       { path: entrypoint, content: file },
       files,
       processedModules,
+      parsedModules,
+      manifest,
     )
+
+    // Items are stored where they're defined, so record the paths users
+    // actually name them by (e.g. through `pub use` in lib.rs)
+    const publicPaths = resolvePublicPaths(parsedModules)
+    const publicPathsKey = `crates/${crateName}/${version}/public_paths.json`
+    try {
+      await this.env.CRATE_BUCKET.put(
+        publicPathsKey,
+        JSON.stringify(Object.fromEntries(publicPaths)),
+      )
+    } catch (error) {
+      throw new Error(
+        `Failed to store public paths at ${publicPathsKey}: ${error}`,
+      )
+    }
+
+    return storedItem
   }
 
   private async parseAndStoreFile(
@@ -146,6 +177,8 @@ export class CrateProcessor {
     file: { path: string; content: string },
     allFiles: Map<string, string>,
     processedModules: Set<string>,
+    parsedModules: Map<string, FileInfo>,
+    manifest: string | undefined,
   ): Promise<StoredModule> {
     async function storeItem(
       itemKey: string,
@@ -175,8 +208,6 @@ export class CrateProcessor {
       submodules: [],
     }
 
-    const currentPath = ['src', ...modulePath].join('/')
-
     // Mark this file as processed to prevent infinite loops
     if (processedModules.has(file.path)) {
       return storedItem
@@ -191,15 +222,21 @@ export class CrateProcessor {
       code: file.content,
       filePath: file.path,
       includePrivate: false, // Only process public items for recursive parsing
+      manifest,
     })
 
     if (!response.success || !response.fileInfo) {
-      console.warn(
-        `Failed to parse file ${file.path}: ${response.errors.join(', ')}`,
+      const errors = response.errors.map((error) =>
+        error.span
+          ? `${error.span.start.line}:${error.span.start.column} ${error.code}: ${error.message}`
+          : `${error.code}: ${error.message}`,
       )
+      console.warn(`Failed to parse file ${file.path}: ${errors.join(', ')}`)
       return storedItem
     }
 
+    parsedModules.set(modulePath.join('::'), response.fileInfo)
+
     // save the module summary
 
     try {
@@ -217,7 +254,7 @@ export class CrateProcessor {
     for (const item of items) {
       const itemType = this.getItemType(item)
 
-      const itemKey = `crates/${crateName}/${version}/${modulePath.join('/')}/${item.name}.json`
+      const itemKey = `crates/${crateName}/${version}/${[...modulePath, item.name].join('/')}.json`
       switch (itemType) {
         case 'function':
           await storeItem(itemKey, item, this.env.CRATE_BUCKET)
@@ -233,6 +270,10 @@ export class CrateProcessor {
           await storeItem(itemKey, item, this.env.CRATE_BUCKET)
           storedItem.items.push(itemKey)
           break
+        case 'macro':
+          await storeItem(itemKey, item, this.env.CRATE_BUCKET)
+          storedItem.items.push(itemKey)
+          break
         default:
           break
       }
@@ -240,20 +281,19 @@ export class CrateProcessor {
 
     // Recursively process module references
     for (const moduleRef of moduleReferences) {
-      if (moduleRef.visibility.includes('pub')) {
+      if (moduleRef.visibility === 'public') {
         const newModulePath: string[] = [...modulePath, moduleRef.name]
-        // Find the referenced module file
-        const moduleFile = moduleRef.expectedPaths.find((path) => {
-          const filePath = `${currentPath}/${path}`
-          return allFiles.has(filePath)
-        })
-        if (!moduleFile) {
+        // Find the referenced module file; the parser resolves expected
+        // paths against the path of the file being parsed
+        const path = moduleRef.expectedPaths.find((filePath) =>
+          allFiles.has(filePath),
+        )
+        if (!path) {
           console.warn(
             `Module reference ${moduleRef.name} not found in provided files`,
           )
           continue
         }
-        const path = `${currentPath}/${moduleFile}`
         const content = allFiles.get(path)!
 
         const recursiveStoredItem = await this.parseAndStoreFile(
@@ -264,6 +304,8 @@ export class CrateProcessor {
           { path, content },
           allFiles,
           processedModules,
+          parsedModules,
+          manifest,
         )
         storedItem.submodules.push(recursiveStoredItem)
       }
@@ -276,6 +318,12 @@ export class CrateProcessor {
     if ('function' in item.details) return 'function'
     if ('adt' in item.details) return item.details.adt.adtType
     if ('trait' in item.details) return 'trait'
+    if ('impl' in item.details) return 'impl'
+    if ('typeAlias' in item.details) return 'type_alias'
+    if ('macro' in item.details) return 'macro'
+    if ('externBlock' in item.details) return 'extern_block'
+    if ('externCrate' in item.details) return 'extern_crate'
+    if ('use' in item.details) return 'use'
     if ('module' in item.details) return 'module'
     if ('other' in item.details) return item.details.other.itemType
     return 'unknown'
@@ -444,6 +492,7 @@ export class CrateProcessingWorkflow extends WorkflowEntrypoint<
             crateName,
             version,
             crateData.files,
+            crateData.manifest,
           )
         },
       )
@@ -1,4 +1,4 @@
-import type { FileInfo, ItemInfo } from '@riddick/types'
+import type { DocSections, FileInfo, ItemInfo } from '@riddick/types'
 
 /**
  * System prompt defining the AI's persona and rules.
@@ -10,6 +10,26 @@ export function getSystemPrompt(): string {
 CRITICAL RULE: If the provided doc comments are already clear, comprehensive, and sufficient to understand the item, you MUST respond with the single phrase: "NO_SUMMARY_NEEDED". Otherwise, provide your summary.`
 }
 
+/**
+ * Lists the errors, panics and safety requirements the docs already spell
+ * out, so the model doesn't have to re-derive them from the source.
+ */
+function describeDocumentedContract(
+  doc_sections: DocSections | null | undefined,
+): string {
+  const sections = [
+    ['Errors', doc_sections?.errors],
+    ['Panics', doc_sections?.panics],
+    ['Safety', doc_sections?.safety],
+  ].filter(([, content]) => content)
+
+  if (sections.length === 0) return ''
+  return `
+**Documented Contract:**
+${sections.map(([title, content]) => `${title}:\n${content}`).join('\n\n')}
+`
+}
+
 /**
  * Creates a user prompt for summarizing a Rust function.
  */
@@ -17,6 +37,7 @@ export function getFunctionSummaryPrompt(
   signature: string,
   doc_comment: string | null,
   source_code: string,
+  doc_sections?: DocSections | null,
 ): string {
   return `Analyze the following Rust function.
 
@@ -34,9 +55,9 @@ ${doc_comment || 'N/A'}
 \`\`\`rust
 ${source_code}
 \`\`\`
-
+${describeDocumentedContract(doc_sections)}
 **Task:**
-Based on the context, provide a concise, technical summary. Explain what the function does, its parameters, what it returns, and any key logic, side effects, or error conditions.`
+Based on the context, provide a concise, technical summary. Explain what the function does, its parameters, what it returns, and any key logic, side effects, or error conditions. Where the documented contract already covers errors, panics or safety requirements, rely on it rather than re-deriving them.`
 }
 
 /**
@@ -61,6 +82,31 @@ function createAdtSkeleton(items: ItemInfo[]): string {
     .join('\n')
 }
 
+/**
+ * Describes the attributes that change how a type can be used.
+ * @param {ItemInfo} item - The struct or enum item.
+ * @returns {string} One line per relevant attribute, or 'N/A'.
+ */
+function describeKnownAttributes(item: ItemInfo): string {
+  const known = item.knownAttributes
+  const lines: string[] = []
+  if (known.derives.length > 0) {
+    lines.push(`Derives: ${known.derives.join(', ')}`)
+  }
+  if (known.isNonExhaustive) {
+    lines.push(
+      '#[non_exhaustive]: cannot be constructed or exhaustively matched outside its crate',
+    )
+  }
+  if (known.repr.length > 0) {
+    lines.push(`Representation: #[repr(${known.repr.join(', ')})]`)
+  }
+  if (known.deprecated) {
+    lines.push(`Deprecated: ${known.deprecated.note || 'no reason given'}`)
+  }
+  return lines.length > 0 ? lines.join('\n') : 'N/A'
+}
+
 /**
  * Creates a user prompt for summarizing a Rust struct or enum.
  */
@@ -84,6 +130,9 @@ ${source_code}
 ${doc_comment || 'N/A'}
 \`\`\`
 
+**Attributes:**
+${describeKnownAttributes(item)}
+
 **Public API:**
 \`\`\`rust
 ${skeleton}
@@ -117,6 +166,57 @@ ${doc_comment || 'N/A'}
 Based on the context, provide a concise, technical summary. Explain the abstract behavior or contract that this trait defines. What capabilities does a type gain by implementing it? What is the purpose of its methods and any associated types?`
 }
 
+/**
+ * Describes how a macro is invoked: each rule's call syntax for declarative
+ * macros, or the derive/attribute/call form for proc macros.
+ */
+function describeMacroUsage(item: ItemInfo): string {
+  if (!('macro' in item.details)) return 'N/A'
+  const details = item.details.macro
+
+  switch (details.kind) {
+    case 'procMacro':
+      return `${item.name}!(...)`
+    case 'procMacroDerive': {
+      const helpers = details.helperAttributes.map((helper) => `#[${helper}]`)
+      return helpers.length > 0
+        ? `#[derive(${item.name})]\n// helper attributes: ${helpers.join(', ')}`
+        : `#[derive(${item.name})]`
+    }
+    case 'procMacroAttribute':
+      return `#[${item.name}]`
+    default:
+      return details.rules.map((rule) => rule.callSyntax).join('\n') || 'N/A'
+  }
+}
+
+/**
+ * Creates a user prompt for summarizing a macro.
+ */
+export function getMacroSummaryPrompt(item: ItemInfo): string {
+  const callSyntax = describeMacroUsage(item)
+
+  return `Analyze the following Rust macro.
+
+**Call Syntax:**
+\`\`\`rust
+${callSyntax}
+\`\`\`
+
+**Doc Comments:**
+\`\`\`
+${item.docComment || 'N/A'}
+\`\`\`
+
+**Definition:**
+\`\`\`rust
+${item.fullCode}
+\`\`\`
+
+**Task:**
+Based on the context, provide a concise, technical summary. Explain what the macro expands to and how it is meant to be invoked, covering each accepted form of input. For procedural macros, describe usage from the caller's side rather than the \`TokenStream\` handling.`
+}
+
 /**
  * Generates a text skeleton of a module's public API from its items.
  * @param {ItemInfo[]} items - The array of items within the module.
@@ -132,8 +232,15 @@ function createModuleSkeleton(items: ItemInfo[]): string {
       if ('adt' in item.details) {
         return `/// ${item.docComment?.split('\n').join('///') || ''}\n${item.name}`
       }
+      if ('externBlock' in item.details) {
+        const { abi, items: foreignItems } = item.details.externBlock
+        const signatures = foreignItems.map(
+          (foreign) => `    ${foreign.signature}`,
+        )
+        return `extern "${abi}" {\n${signatures.join('\n')}\n}`
+      }
 
-      return null // Skip items that are not functions or structs/enums
+      return null // Skip items that are not functions, structs/enums or FFI declarations
     })
     .filter(Boolean)
     .join('\n')
@@ -151,20 +258,30 @@ export function getModuleSummaryPrompt(
 
 **Module Path:** \`${module_path}\`
 
+**Module Docs:**
+\`\`\`
+${module_info.docComment || 'N/A'}
+\`\`\`
+
 **Public API:**
 \`\`\`rust
 ${module_skeleton}
 \`\`\`
 
 **Task:**
-Based on the public items it exposes, provide a high-level technical summary of this module's purpose and responsibility. What kind of functionality does it group together? How do its parts seem to relate to each other?`
+Based on its documentation and the public items it exposes, provide a high-level technical summary of this module's purpose and responsibility. What kind of functionality does it group together? How do its parts seem to relate to each other?`
 }
 
 export function getPromptForItem(item: ItemInfo): string | null {
   if ('function' in item.details) {
     const _functionDetails = item.details.function
 
-    return getFunctionSummaryPrompt(item.name, item.docComment, item.fullCode)
+    return getFunctionSummaryPrompt(
+      item.name,
+      item.docComment,
+      item.fullCode,
+      item.docSections,
+    )
   }
   if ('adt' in item.details) {
     return getStructOrEnumSummaryPrompt(item)
@@ -172,5 +289,8 @@ export function getPromptForItem(item: ItemInfo): string | null {
   if ('trait' in item.details) {
     return getTraitSummaryPrompt(item.fullCode, item.docComment)
   }
+  if ('macro' in item.details) {
+    return getMacroSummaryPrompt(item)
+  }
   return null
 }
@@ -0,0 +1,209 @@
+import type { FileInfo, ImportInfo, ItemInfo } from '@riddick/types'
+
+// What a name in a module's namespace refers to, by its defining path
+type Target = { kind: 'item' | 'module'; path: string }
+
+function joinPath(module: string, name: string): string {
+  return module ? `${module}::${name}` : name
+}
+
+function parentPath(module: string): string {
+  const index = module.lastIndexOf('::')
+  return index === -1 ? '' : module.slice(0, index)
+}
+
+function lastSegment(module: string): string {
+  return module.split('::').pop()!
+}
+
+function isImport(item: ItemInfo): boolean {
+  return (
+    'use' in item.details ||
+    ('other' in item.details && item.details.other.itemType === 'use')
+  )
+}
+
+// Items that don't add a name to the module's namespace
+function isUnnamed(item: ItemInfo): boolean {
+  return 'impl' in item.details || 'externBlock' in item.details
+}
+
+// `#[macro_export]` puts a `macro_rules!` macro at the crate root, whichever
+// module it's written in
+function isExportedMacro(item: ItemInfo): boolean {
+  return (
+    'macro' in item.details &&
+    item.details.macro.kind === 'macroRules' &&
+    item.details.macro.isExported
+  )
+}
+
+function reexportsOf(items: ItemInfo[]): ImportInfo[] {
+  return items.flatMap((item) =>
+    'use' in item.details
+      ? item.details.use.imports.filter((i) => i.isReexport)
+      : [],
+  )
+}
+
+// The items of every module, including inline `mod m { ... }` modules,
+// keyed by module path
+function collectModuleItems(
+  modules: Map<string, FileInfo>,
+): Map<string, ItemInfo[]> {
+  const moduleItems = new Map<string, ItemInfo[]>()
+  const collect = (module: string, items: ItemInfo[]) => {
+    moduleItems.set(module, items)
+    for (const item of items) {
+      if ('module' in item.details) {
+        collect(joinPath(module, item.name), item.details.module.items)
+      }
+    }
+  }
+  for (const [module, fileInfo] of modules) {
+    collect(module, fileInfo.items)
+  }
+  return moduleItems
+}
+
+/**
+ * Resolves an import path, as written in `module`, to what it names.
+ *
+ * Paths into other crates, or into modules that weren't parsed, resolve to
+ * `undefined`.
+ */
+function resolveImportPath(
+  importPath: string,
+  module: string,
+  exports: Map<string, Map<string, Target>>,
+): Target | undefined {
+  const segments = importPath.split('::')
+  let current = module
+  let index = 0
+
+  if (segments[0] === 'crate') {
+    current = ''
+    index = 1
+  }
+  while (segments[index] === 'self' || segments[index] === 'super') {
+    if (segments[index] === 'super') {
+      if (current === '') return undefined
+      current = parentPath(current)
+    }
+    index++
+  }
+  if (index === segments.length) {
+    return { kind: 'module', path: current }
+  }
+
+  for (; index < segments.length; index++) {
+    const target = exports.get(current)?.get(segments[index])
+    if (!target) return undefined
+    if (index === segments.length - 1) return target
+    if (target.kind !== 'module') return undefined
+    current = target.path
+  }
+  return undefined
+}
+
+/**
+ * Works out the public paths each item of a crate is reachable under.
+ *
+ * `modules` maps module paths (`''` for the crate root, `a::b` below it) to
+ * their parsed files; inline modules inside them are modules of their own.
+ * Names are followed through chains of `pub use`, glob re-exports and
+ * `self`/`super`/`crate` prefixes until nothing new resolves; explicit
+ * re-exports shadow names brought in by globs, as in rustc.
+ *
+ * Returns the public paths of every reachable item, shortest first, keyed by
+ * the path the item is defined at.
+ */
+export function resolvePublicPaths(
+  modules: Map<string, FileInfo>,
+): Map<string, string[]> {
+  const moduleItems = collectModuleItems(modules)
+  const exports = new Map<string, Map<string, Target>>()
+  for (const module of moduleItems.keys()) {
+    exports.set(module, new Map())
+  }
+  for (const [module, items] of moduleItems) {
+    const names = exports.get(module)!
+    for (const item of items) {
+      if (isImport(item) || isUnnamed(item)) continue
+      const path = joinPath(module, item.name)
+      if ('module' in item.details) {
+        names.set(item.name, { kind: 'module', path })
+      } else if (isExportedMacro(item)) {
+        exports.get('')?.set(item.name, { kind: 'item', path })
+      } else {
+        names.set(item.name, { kind: 'item', path })
+      }
+    }
+  }
+  // Modules in their own files aren't items of their parent
+  for (const module of modules.keys()) {
+    if (module === '') continue
+    exports
+      .get(parentPath(module))
+      ?.set(lastSegment(module), { kind: 'module', path: module })
+  }
+
+  const globNames = new Map<string, Set<string>>()
+  let changed = true
+  while (changed) {
+    changed = false
+    for (const [module, items] of moduleItems) {
+      const names = exports.get(module)!
+      const fromGlobs = globNames.get(module) ?? new Set<string>()
+      globNames.set(module, fromGlobs)
+
+      for (const reexport of reexportsOf(items)) {
+        const target = resolveImportPath(reexport.path, module, exports)
+        if (!target) continue
+
+        if (!reexport.isGlob) {
+          const name = reexport.alias ?? reexport.name
+          if (name === '_') continue
+          if (!names.has(name) || fromGlobs.has(name)) {
+            changed ||= names.get(name)?.path !== target.path
+            names.set(name, target)
+            fromGlobs.delete(name)
+          }
+          continue
+        }
+
+        if (target.kind !== 'module') continue
+        for (const [name, globTarget] of exports.get(target.path) ?? []) {
+          if (names.has(name)) continue
+          names.set(name, globTarget)
+          fromGlobs.add(name)
+          changed = true
+        }
+      }
+    }
+  }
+
+  // Walk the namespaces from the crate root, breadth first so that shorter
+  // paths come first. A module is never re-entered from within itself.
+  const publicPaths = new Map<string, string[]>()
+  const queue = [{ module: '', path: '', ancestors: [''] }]
+  while (queue.length > 0) {
+    const { module, path, ancestors } = queue.shift()!
+    for (const [name, target] of exports.get(module) ?? []) {
+      const publicPath = joinPath(path, name)
+      if (target.kind === 'item') {
+        const paths = publicPaths.get(target.path) ?? []
+        paths.push(publicPath)
+        publicPaths.set(target.path, paths)
+      } else if (!ancestors.includes(target.path)) {
+        queue.push({
+          module: target.path,
+          path: publicPath,
+          ancestors: [...ancestors, target.path],
+        })
+      }
+    }
+  }
+
+  return publicPaths
+}
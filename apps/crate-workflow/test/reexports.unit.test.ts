@@ -0,0 +1,145 @@
+import { describe, it, expect } from 'vitest'
+import type {
+  FileInfo,
+  FunctionDetails,
+  ItemDetails,
+  ItemInfo,
+} from '@riddick/types'
+import { resolvePublicPaths } from '../src/reexports'
+
+// Only the name and details matter for resolving paths
+function item(name: string, details: ItemDetails): ItemInfo {
+  return { name, details } as ItemInfo
+}
+
+function fn(name: string): ItemInfo {
+  return item(name, { function: {} as FunctionDetails })
+}
+
+function mod(name: string, items: ItemInfo[]): ItemInfo {
+  return item(name, {
+    module: { docComment: null, items, moduleReferences: [] },
+  })
+}
+
+function exportedMacro(name: string): ItemInfo {
+  return item(name, {
+    macro: {
+      kind: 'macroRules',
+      isExported: true,
+      rules: [],
+      helperAttributes: [],
+      functionName: null,
+    },
+  })
+}
+
+// `pub use path;`, `pub use path as alias;` or `pub use path::*;`
+function pubUse(path: string, alias: string | null = null): ItemInfo {
+  const isGlob = path.endsWith('::*')
+  const importPath = isGlob ? path.slice(0, -'::*'.length) : path
+  return item(path, {
+    use: {
+      imports: [
+        {
+          path: importPath,
+          name: isGlob ? '*' : importPath.split('::').pop()!,
+          alias,
+          isGlob,
+          isReexport: true,
+        },
+      ],
+    },
+  })
+}
+
+function resolve(modules: Record<string, ItemInfo[]>) {
+  const files = new Map<string, FileInfo>(
+    Object.entries(modules).map(([module, items]) => [
+      module,
+      { docComment: null, items, moduleReferences: [] },
+    ]),
+  )
+  return Object.fromEntries(resolvePublicPaths(files))
+}
+
+describe('resolvePublicPaths', () => {
+  it('should follow chains of re-exports', () => {
+    const paths = resolve({
+      '': [pubUse('a::Thing')],
+      a: [pubUse('crate::b::Thing')],
+      b: [fn('Thing')],
+    })
+
+    expect(paths['b::Thing']).toEqual(['Thing', 'a::Thing', 'b::Thing'])
+  })
+
+  it('should bring in every name of a glob re-export', () => {
+    const paths = resolve({
+      '': [pubUse('inner::*')],
+      inner: [fn('f'), fn('g')],
+    })
+
+    expect(paths['inner::f']).toEqual(['f', 'inner::f'])
+    expect(paths['inner::g']).toEqual(['g', 'inner::g'])
+  })
+
+  it('should resolve self, super and crate prefixes', () => {
+    const paths = resolve({
+      '': [pubUse('self::a::b::helper', 'renamed')],
+      a: [fn('helper')],
+      'a::b': [pubUse('super::helper'), pubUse('crate::a::b::helper', 'again')],
+    })
+
+    expect(paths['a::helper']).toEqual([
+      'renamed',
+      'a::helper',
+      'a::b::helper',
+      'a::b::again',
+    ])
+  })
+
+  it('should let explicit names shadow glob names', () => {
+    const paths = resolve({
+      '': [pubUse('one::*'), pubUse('two::Name'), fn('local')],
+      one: [fn('Name'), fn('local')],
+      two: [fn('Name')],
+    })
+
+    expect(paths['two::Name']).toEqual(['Name', 'two::Name'])
+    expect(paths['one::Name']).toEqual(['one::Name'])
+    expect(paths['local']).toEqual(['local'])
+    expect(paths['one::local']).toEqual(['one::local'])
+  })
+
+  it('should terminate on cyclic re-exports', () => {
+    const paths = resolve({
+      '': [pubUse('crate', 'root')],
+      a: [fn('x'), pubUse('crate::b::*')],
+      b: [fn('y'), pubUse('crate::a::*')],
+    })
+
+    expect(paths['a::x']).toEqual(['a::x', 'b::x'])
+    expect(paths['b::y']).toEqual(['a::y', 'b::y'])
+  })
+
+  it('should resolve names in inline modules', () => {
+    const paths = resolve({
+      '': [mod('m', [fn('f'), mod('nested', [fn('g')])]), pubUse('m::f')],
+      a: [pubUse('crate::m::nested::g')],
+    })
+
+    expect(paths['m::f']).toEqual(['f', 'm::f'])
+    expect(paths['m::nested::g']).toEqual(['a::g', 'm::nested::g'])
+  })
+
+  it('should only give exported macros a path at the crate root', () => {
+    const paths = resolve({
+      '': [],
+      a: [exportedMacro('my_macro'), mod('inner', [exportedMacro('nested')])],
+    })
+
+    expect(paths['a::my_macro']).toEqual(['my_macro'])
+    expect(paths['a::inner::nested']).toEqual(['nested'])
+  })
+})
@@ -95,11 +95,26 @@ describe('crate-workflow', () => {
       'crate.json',
       'expressions.json',
       'expressions/parse_expression.json',
+      'public_paths.json',
       'types.json',
       'types/parse_symbol.json',
       'types/parse_type.json',
     ])
 
+    // re-exports in lib.rs make items reachable from the crate root
+    const publicPathsObj = await env.CRATE_BUCKET.get(
+      `${prefix}public_paths.json`,
+    )
+    const publicPaths: Record<string, string[]> = await publicPathsObj!.json()
+    expect(publicPaths['expressions::parse_expression']).toStrictEqual([
+      'parse_expression',
+      'expressions::parse_expression',
+    ])
+    expect(publicPaths['types::parse_type']).toStrictEqual([
+      'parse_type',
+      'types::parse_type',
+    ])
+
     for (const file of files.objects) {
       const content = await env.CRATE_BUCKET.get(file.key)
       expect(content).toBeDefined()
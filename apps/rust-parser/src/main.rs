@@ -4,17 +4,61 @@ use std::path::Path;
 
 mod parser;
 
-use parser::parse_rust_code;
+use parser::{parse_rust_code, Edition, ParseRequest};
 
 fn main() {
     let args: Vec<String> = env::args().collect();
     if args.len() < 2 {
-        eprintln!("Usage: {} <rust_file_path> [--include-private]", args[0]);
+        eprintln!(
+            "Usage: {} <rust_file_path> [--include-private] [--cfg <spec>]... [--edition <year>] [--manifest-path <Cargo.toml>]",
+            args[0]
+        );
         std::process::exit(1);
     }
 
     let file_path = &args[1];
-    let include_private = args.get(2).is_some_and(|arg| arg == "--include-private");
+    let mut include_private = false;
+    let mut cfg = Vec::new();
+    let mut edition = None;
+    let mut manifest_path = None;
+
+    let mut options = args[2..].iter();
+    while let Some(arg) = options.next() {
+        match arg.as_str() {
+            "--include-private" => include_private = true,
+            "--cfg" => match options.next() {
+                Some(spec) => cfg.push(spec.clone()),
+                None => {
+                    eprintln!("Error: --cfg expects a value, e.g. --cfg 'feature=\"std\"'");
+                    std::process::exit(1);
+                }
+            },
+            "--edition" => match options.next() {
+                Some(year) => match Edition::from_year(year) {
+                    Some(parsed) => edition = Some(parsed),
+                    None => {
+                        eprintln!("Error: Unknown edition '{year}', expected e.g. 2021");
+                        std::process::exit(1);
+                    }
+                },
+                None => {
+                    eprintln!("Error: --edition expects a value, e.g. --edition 2021");
+                    std::process::exit(1);
+                }
+            },
+            "--manifest-path" => match options.next() {
+                Some(path) => manifest_path = Some(path.clone()),
+                None => {
+                    eprintln!("Error: --manifest-path expects a path to a Cargo.toml");
+                    std::process::exit(1);
+                }
+            },
+            _ => {
+                eprintln!("Error: Unknown option '{arg}'");
+                std::process::exit(1);
+            }
+        }
+    }
 
     if !Path::new(file_path).exists() {
         eprintln!("Error: File '{file_path}' does not exist");
@@ -29,8 +73,29 @@ fn main() {
         }
     };
 
-    match parse_rust_code(&code, include_private) {
-        // Include private items by default for CLI
+    // The crate's edition comes from its manifest unless given explicitly
+    let manifest = match manifest_path {
+        Some(path) => match fs::read_to_string(&path) {
+            Ok(content) => Some(content),
+            Err(err) => {
+                eprintln!("Error reading manifest '{path}': {err}");
+                std::process::exit(1);
+            }
+        },
+        None => None,
+    };
+
+    let request = ParseRequest {
+        code,
+        file_path: Some(file_path.clone()),
+        include_private,
+        // Without any `--cfg`, report cfg'd items as active instead of evaluating them
+        cfg: (!cfg.is_empty()).then_some(cfg),
+        edition,
+        manifest,
+    };
+
+    match parse_rust_code(&request) {
         Ok(response) => {
             // Pretty print the JSON output
             match serde_json::to_string_pretty(&response) {
@@ -1,17 +1,73 @@
 use ra_ap_syntax::{
-    ast::{self, HasDocComments, HasModuleItem, HasName, HasVisibility},
-    AstNode, AstToken, SourceFile, TextRange,
+    ast::{self, HasGenericParams, HasModuleItem, HasName, HasTypeBounds, HasVisibility},
+    AstNode, SourceFile, SyntaxKind, TextRange,
 };
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use ts_rs::TS;
 
+mod attributes;
+mod cfg;
+mod docs;
+mod line_index;
+mod manifest;
+
+use attributes::{extract_attributes, extract_links, extract_path_attribute, unquote};
+use cfg::{CfgExpr, CfgOptions};
+use docs::{extract_doc_comment, extract_doctests, extract_inner_doc_comment, parse_doc_sections};
+use line_index::LineIndex;
+use manifest::manifest_edition;
+
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 #[ts(export)]
 #[serde(rename_all = "camelCase")]
 pub struct ParseRequest {
     pub code: String,
-    pub file_path: Option<String>, // Optional file path for context
+    pub file_path: Option<String>, // e.g. src/foo.rs; module paths are resolved against it
     pub include_private: bool,     // Whether to include private items
+    pub cfg: Option<Vec<String>>,  // `--cfg` options like `feature="std"`; None skips cfg checks
+    pub edition: Option<Edition>,  // Overrides the edition read from `manifest`
+    pub manifest: Option<String>,  // Contents of the crate's Cargo.toml, for its edition
+}
+
+/// The Rust edition a file is parsed with.
+///
+/// Editions change what is a keyword (`async`, `dyn`, `gen`, ...), so
+/// older crates can misparse under a newer one.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub enum Edition {
+    #[serde(rename = "2015")]
+    Edition2015,
+    #[serde(rename = "2018")]
+    Edition2018,
+    #[serde(rename = "2021")]
+    Edition2021,
+    #[default]
+    #[serde(rename = "2024")]
+    Edition2024,
+}
+
+impl Edition {
+    /// Parses an edition as written in Cargo.toml or on the command line: `2021`.
+    pub fn from_year(year: &str) -> Option<Self> {
+        match year {
+            "2015" => Some(Edition::Edition2015),
+            "2018" => Some(Edition::Edition2018),
+            "2021" => Some(Edition::Edition2021),
+            "2024" => Some(Edition::Edition2024),
+            _ => None,
+        }
+    }
+
+    fn to_syntax(self) -> ra_ap_syntax::Edition {
+        match self {
+            Edition::Edition2015 => ra_ap_syntax::Edition::Edition2015,
+            Edition::Edition2018 => ra_ap_syntax::Edition::Edition2018,
+            Edition::Edition2021 => ra_ap_syntax::Edition::Edition2021,
+            Edition::Edition2024 => ra_ap_syntax::Edition::Edition2024,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, TS)]
@@ -28,7 +84,8 @@ pub struct ParseResponse {
 #[ts(export)]
 #[serde(rename_all = "camelCase")]
 pub struct FileInfo {
-    pub items: Vec<ItemInfo>, // All items including inline modules
+    pub doc_comment: Option<String>, // Inner `//!` docs of the file, i.e. the module's docs
+    pub items: Vec<ItemInfo>,        // All items including inline modules
     pub module_references: Vec<ModuleReference>, // Modules declared with `mod foo;`
 }
 
@@ -41,6 +98,7 @@ pub struct ModuleInfo {
     pub inline_modules: Vec<ModuleInfo>, // Nested inline modules
     pub module_references: Vec<ModuleReference>, // Referenced modules
     pub location: [u32; 2],   // [start_byte, end_byte] in the file
+    pub span: SourceSpan,     // Line/column form of `location`
 }
 
 #[derive(Debug, Clone, Serialize, TS)]
@@ -48,9 +106,14 @@ pub struct ModuleInfo {
 #[serde(rename_all = "camelCase")]
 pub struct ModuleReference {
     pub name: String,
-    pub visibility: String,          // "pub", "pub(crate)", "private", etc.
-    pub expected_paths: Vec<String>, // Potential file paths (foo.rs, foo/mod.rs)
-    pub location: [u32; 2],          // [start_byte, end_byte] in the file
+    pub visibility: Visibility,
+    pub is_public_api: bool, // Whether the referenced module is part of the public API
+    pub expected_paths: Vec<String>, // Potential file paths (src/foo.rs, src/foo/mod.rs)
+    pub path_attribute: Option<String>, // Raw `#[path = "..."]`, resolved into `expected_paths`
+    pub cfg: Option<String>, // Raw `#[cfg(...)]` predicate, if any
+    pub is_cfg_active: bool, // Whether `cfg` holds under the requested options
+    pub location: [u32; 2],  // [start_byte, end_byte] in the file
+    pub span: SourceSpan,    // Line/column form of `location`
 }
 
 #[derive(Debug, Clone, Serialize, TS)]
@@ -60,11 +123,55 @@ pub struct ItemInfo {
     pub name: String,
     pub full_code: String,
     pub doc_comment: Option<String>,
-    pub visibility: String, // "pub", "pub(crate)", "pub(super)", "private", etc.
-    pub location: [u32; 2], // [start_byte, end_byte]
+    pub doc_sections: Option<DocSections>, // `doc_comment` split into rustdoc's sections
+    pub doctests: Vec<Doctest>,            // Fenced code blocks in `doc_comment`
+    pub visibility: Visibility,
+    pub is_public_api: bool, // Reachable from outside the crate and not `#[doc(hidden)]`
+    pub attributes: Vec<AttributeInfo>,
+    pub known_attributes: KnownAttributes, // Attributes that change how the item is used
+    pub cfg: Option<String>,               // Raw `#[cfg(...)]` predicate, if any
+    pub is_cfg_active: bool,               // Whether `cfg` holds under the requested options
+    pub location: [u32; 2],                // [start_byte, end_byte]
+    pub span: SourceSpan,                  // Line/column form of `location`
     pub details: ItemDetails,
 }
 
+#[derive(Debug, Clone, Default, Serialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub struct DocSections {
+    pub summary: Option<String>, // The first paragraph, joined into one line
+    pub body: Option<String>,    // Everything outside the summary and the sections below
+    pub examples: Option<String>,
+    pub errors: Option<String>,
+    pub panics: Option<String>,
+    pub safety: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub struct Doctest {
+    pub language: String,        // "rust" unless tagged with another language
+    pub attributes: Vec<String>, // ignore, no_run, should_panic, edition2021, ...
+    pub code: String,            // The code as compiled, hidden `# ` lines included
+    pub visible_code: String,    // The code as rendered, hidden lines removed
+    pub location: [u32; 2],      // [start_byte, end_byte] of the fenced lines
+    pub span: SourceSpan,        // Line/column form of `location`
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub enum Visibility {
+    Public,         // pub
+    Crate,          // pub(crate)
+    Super,          // pub(super)
+    InPath(String), // pub(in path), with the path as written
+    Private,        // No visibility, or pub(self)
+    Inherited,      // Impls, trait impl items and variant fields, which can't declare their own
+}
+
 #[derive(Debug, Clone, Serialize, TS)]
 #[ts(export)]
 #[serde(rename_all = "camelCase")]
@@ -72,6 +179,12 @@ pub enum ItemDetails {
     Function(FunctionDetails),
     Adt(AdtDetails), // Algebraic Data Type (struct, enum, union)
     Trait(TraitDetails),
+    Impl(ImplDetails),
+    TypeAlias(TypeAliasDetails),
+    Macro(MacroDetails),
+    ExternBlock(ExternBlockDetails),
+    ExternCrate(ExternCrateDetails),
+    Use(UseDetails),
     Module(ModuleDetails),
     Other(OtherDetails),
 }
@@ -81,28 +194,346 @@ pub enum ItemDetails {
 #[serde(rename_all = "camelCase")]
 pub struct FunctionDetails {
     pub signature: String,
+    pub is_async: bool,
+    pub is_const: bool,
+    pub is_unsafe: bool,
+    pub abi: Option<String>, // "C" for both `extern "C"` and a bare `extern`
+    pub receiver: Option<Receiver>,
+    pub params: Vec<FunctionParam>, // Parameters after the receiver
+    pub return_type: Option<String>,
+    pub generics: Generics,
+    pub impl_origin: Option<ImplOrigin>, // Set for methods, None for free functions
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub enum ImplOrigin {
+    Inherent,      // impl Foo { ... }
+    Trait(String), // impl Trait for Foo { ... }, with the trait path as written
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub enum Receiver {
+    Value,         // self, mut self
+    Ref,           // &self, &'a self
+    RefMut,        // &mut self
+    Typed(String), // self: Box<Self>, self: Pin<&mut Self>, ...
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub struct FunctionParam {
+    pub pattern: Option<String>, // None for anonymous or variadic params
+    #[serde(rename = "type")]
+    pub ty: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub struct Generics {
+    pub params: Vec<GenericParam>,
+    pub where_predicates: Vec<WherePredicate>,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub struct GenericParam {
+    pub name: String, // "T", "'a", "N"
+    pub kind: GenericParamKind,
+    pub bounds: Vec<String>, // Inline bounds: `T: Clone + Send`, `'a: 'b`
+    #[serde(rename = "type")]
+    pub ty: Option<String>, // Type of a const param
+    pub default: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub enum GenericParamKind {
+    Type,
+    Lifetime,
+    Const,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub struct WherePredicate {
+    pub target: String, // Bounded type or lifetime, including any `for<'a>` binder
+    pub bounds: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, TS)]
 #[ts(export)]
 #[serde(rename_all = "camelCase")]
 pub struct AdtDetails {
-    pub adt_type: String,       // "struct", "enum", "union"
-    pub methods: Vec<ItemInfo>, // Methods from impl blocks
+    pub adt_type: String, // "struct", "enum", "union"
+    pub generics: Generics,
+    pub field_kind: Option<FieldListKind>, // Shape of a struct or union body, None for enums
+    pub fields: Vec<FieldInfo>,            // Struct and union fields
+    pub variants: Vec<VariantInfo>,        // Enum variants
+    pub trait_impls: Vec<TraitImplInfo>,   // `impl Trait for Type` blocks in this file
+    pub methods: Vec<ItemInfo>,            // Methods from impl blocks
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub struct TraitImplInfo {
+    pub trait_path: String, // As written, including generic arguments
+    pub generics: Generics, // Parameters and where-clauses of the impl block
+    pub is_negative: bool,  // impl !Send for Foo
+    pub is_unsafe: bool,    // unsafe impl Send for Foo
+    pub location: [u32; 2], // [start_byte, end_byte]
+    pub span: SourceSpan,   // Line/column form of `location`
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub enum FieldListKind {
+    Named, // struct Foo { a: u32 }
+    Tuple, // struct Foo(u32);
+    Unit,  // struct Foo;
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldInfo {
+    pub name: String, // Field name, or its index for tuple fields
+    #[serde(rename = "type")]
+    pub ty: String,
+    pub visibility: Visibility,
+    pub doc_comment: Option<String>,
+    pub attributes: Vec<AttributeInfo>,
+    pub location: [u32; 2], // [start_byte, end_byte]
+    pub span: SourceSpan,   // Line/column form of `location`
+}
+
+#[derive(Debug, Clone, Default, Serialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub struct KnownAttributes {
+    pub derives: Vec<String>, // #[derive(Debug, Clone)]
+    pub is_must_use: bool,
+    pub is_non_exhaustive: bool,
+    pub inline: Option<InlineKind>,
+    pub repr: Vec<String>, // #[repr(C, u8)]
+    pub deprecated: Option<Deprecation>,
+    pub is_doc_hidden: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub enum InlineKind {
+    Hint,   // #[inline]
+    Always, // #[inline(always)]
+    Never,  // #[inline(never)]
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub struct Deprecation {
+    pub since: Option<String>,
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub struct VariantInfo {
+    pub name: String,
+    pub kind: FieldListKind, // Named for struct-like variants
+    pub fields: Vec<FieldInfo>,
+    pub discriminant: Option<String>, // Explicit `= value`, as written
+    pub doc_comment: Option<String>,
+    pub attributes: Vec<AttributeInfo>,
+    pub location: [u32; 2], // [start_byte, end_byte]
+    pub span: SourceSpan,   // Line/column form of `location`
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub struct AttributeInfo {
+    pub path: String,          // "derive", "serde", "cfg_attr", ...
+    pub args: Vec<String>,     // Top-level arguments of `#[path(a, b = "c")]`
+    pub value: Option<String>, // Value of `#[path = "value"]`, as written
 }
 
 #[derive(Debug, Clone, Serialize, TS)]
 #[ts(export)]
 #[serde(rename_all = "camelCase")]
 pub struct TraitDetails {
+    pub is_unsafe: bool, // unsafe trait Foo
+    pub is_auto: bool,   // auto trait Foo
+    pub generics: Generics,
+    pub supertraits: Vec<String>, // trait Foo: Bar + Baz
+    pub associated_types: Vec<AssocTypeInfo>,
+    pub associated_consts: Vec<AssocConstInfo>,
     pub methods: Vec<TraitMethodInfo>,
 }
 
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub struct AssocTypeInfo {
+    pub name: String,
+    pub generics: Generics,      // Parameters of a generic associated type
+    pub bounds: Vec<String>,     // type Item: Clone + Send;
+    pub default: Option<String>, // type Item = u32;
+    pub doc_comment: Option<String>,
+    pub location: [u32; 2], // [start_byte, end_byte]
+    pub span: SourceSpan,   // Line/column form of `location`
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub struct AssocConstInfo {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub ty: Option<String>,
+    pub default: Option<String>, // const N: usize = 4;
+    pub doc_comment: Option<String>,
+    pub location: [u32; 2], // [start_byte, end_byte]
+    pub span: SourceSpan,   // Line/column form of `location`
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub struct ImplDetails {
+    pub self_type: String,
+    pub trait_path: Option<String>, // None for inherent impls
+    pub generics: Generics,
+    pub is_negative: bool,
+    pub is_unsafe: bool,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub struct TypeAliasDetails {
+    pub aliased_type: Option<String>,
+    pub generics: Generics,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub struct MacroDetails {
+    pub kind: MacroKind,
+    pub is_exported: bool, // `#[macro_export]`, a `pub` 2.0 macro, or any proc macro
+    pub rules: Vec<MacroRule>, // Empty for proc macros
+    pub helper_attributes: Vec<String>, // Inert attributes a derive macro accepts
+    pub function_name: Option<String>, // The fn implementing a proc macro
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub enum MacroKind {
+    MacroRules,         // macro_rules! foo { ... }
+    DeclMacro,          // macro foo(...) { ... }
+    ProcMacro,          // #[proc_macro], used as foo!(...)
+    ProcMacroDerive,    // #[proc_macro_derive(Foo)], used as #[derive(Foo)]
+    ProcMacroAttribute, // #[proc_macro_attribute], used as #[foo]
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub struct MacroRule {
+    pub matcher: String,     // The matcher as written, delimiters included
+    pub call_syntax: String, // How a call matching this rule looks: foo!($x:expr)
+    pub location: [u32; 2],  // [start_byte, end_byte] of the matcher and transcriber
+    pub span: SourceSpan,    // Line/column form of `location`
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub struct ExternBlockDetails {
+    pub abi: String,          // "C" for a bare `extern`
+    pub is_unsafe: bool,      // `unsafe extern`, required since edition 2024
+    pub links: Vec<LinkInfo>, // `#[link(...)]` attributes on the block
+    pub items: Vec<ForeignItemInfo>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub struct LinkInfo {
+    pub name: Option<String>,
+    pub kind: Option<String>, // "static", "dylib", "framework", "raw-dylib"
+    pub modifiers: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub struct ForeignItemInfo {
+    pub name: String,
+    pub kind: ForeignItemKind,
+    pub signature: String, // The declaration without attributes or docs
+    pub visibility: Visibility,
+    pub link_name: Option<String>, // `#[link_name = "..."]`, the symbol actually linked
+    pub doc_comment: Option<String>,
+    pub location: [u32; 2], // [start_byte, end_byte]
+    pub span: SourceSpan,   // Line/column form of `location`
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub enum ForeignItemKind {
+    Fn,
+    Static,
+    Type,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub struct ExternCrateDetails {
+    pub crate_name: String,
+    pub alias: Option<String>, // `extern crate foo as bar;`, possibly `_`
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub struct UseDetails {
+    pub imports: Vec<ImportInfo>, // One per name the use tree brings into scope
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportInfo {
+    pub path: String, // Path as written, e.g. `crate::types::Path`; the module for globs
+    pub name: String, // Last segment of `path` (`self` resolved), or `*` for globs
+    pub alias: Option<String>, // `use foo::Bar as Baz;`, possibly `_`
+    pub is_glob: bool,
+    pub is_reexport: bool, // Declared with a bare `pub use`, in any of its forms
+}
+
 #[derive(Debug, Clone, Serialize, TS)]
 #[ts(export)]
 #[serde(rename_all = "camelCase")]
 pub struct ModuleDetails {
-    pub items: Vec<ItemInfo>, // Items within the inline module
+    pub doc_comment: Option<String>, // Inner `//!` docs only; the item's `doc_comment` has both
+    pub items: Vec<ItemInfo>,        // Items within the inline module
     pub module_references: Vec<ModuleReference>, // Module references within this module
 }
 
@@ -119,42 +550,140 @@ pub struct OtherDetails {
 pub struct TraitMethodInfo {
     pub name: String,
     pub signature: String,
+    pub is_required: bool, // false when the trait provides a default body
     pub doc_comment: Option<String>,
     pub location: [u32; 2], // [start_byte, end_byte]
+    pub span: SourceSpan,   // Line/column form of `location`
+}
+
+// Byte offsets stay the canonical location; positions are derived from them
+
+#[derive(Debug, Clone, Copy, Serialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub struct Position {
+    pub line: u32,         // 1-based line number
+    pub column: u32,       // 1-based column, counted in characters
+    pub utf16_column: u32, // 1-based column, counted in UTF-16 code units
+    pub utf16_offset: u32, // 0-based offset from the start of the file, in UTF-16 code units
 }
 
-// Removed SourceLocation struct - using [u32; 2] for byte offsets
+#[derive(Debug, Clone, Copy, Serialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub struct SourceSpan {
+    pub start: Position,
+    pub end: Position,
+}
 
 #[derive(Debug, Clone, Serialize, TS)]
 #[ts(export)]
 #[serde(rename_all = "camelCase")]
 pub struct ParseError {
     pub message: String,
+    pub code: ParseErrorCode,
     pub severity: String,
     pub location: Option<[u32; 2]>, // [start_byte, end_byte]
+    pub span: Option<SourceSpan>,
+}
+
+/// Machine-readable category for a syntax error.
+///
+/// `ra_ap_syntax` only gives us a message, so the code is derived from it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub enum ParseErrorCode {
+    Expected,            // "expected SEMICOLON", "expected an item", ...
+    UnmatchedDelimiter,  // "unmatched `}`"
+    UnterminatedLiteral, // strings, chars and block comments missing their end
+    InvalidLiteral,      // malformed numbers, escapes and literal prefixes
+    InvalidVisibility,   // visibility qualifiers where none are allowed
+    Other,
 }
 
-pub fn parse_rust_code(code: &str, include_private: bool) -> Result<ParseResponse, String> {
-    let parsed = SourceFile::parse(code, ra_ap_syntax::Edition::Edition2024);
+impl ParseErrorCode {
+    fn from_message(message: &str) -> Self {
+        let lower = message.to_lowercase();
+        if lower.starts_with("expected") {
+            ParseErrorCode::Expected
+        } else if lower.starts_with("unmatched") {
+            ParseErrorCode::UnmatchedDelimiter
+        } else if lower.starts_with("missing trailing") || lower.contains("to terminate") {
+            ParseErrorCode::UnterminatedLiteral
+        } else if lower.contains("lifetime") {
+            // "Unknown lifetime prefix" isn't about literals, despite the word
+            ParseErrorCode::Other
+        } else if lower.contains("literal")
+            || lower.contains("escape")
+            || lower.contains("digits")
+            || lower.contains("prefix")
+        {
+            ParseErrorCode::InvalidLiteral
+        } else if lower.contains("visibility") {
+            ParseErrorCode::InvalidVisibility
+        } else {
+            ParseErrorCode::Other
+        }
+    }
+}
+
+pub fn parse_rust_code(request: &ParseRequest) -> Result<ParseResponse, String> {
+    let code = request.code.as_str();
+    // An explicit edition wins over the manifest's; with neither, use the latest
+    let edition = request
+        .edition
+        .or_else(|| request.manifest.as_deref().and_then(manifest_edition))
+        .unwrap_or_default();
+    let parsed = SourceFile::parse(code, edition.to_syntax());
     let _syntax_node = parsed.syntax_node();
+    let line_index = LineIndex::new(code);
 
     // Extract errors
     let errors: Vec<ParseError> = parsed
         .errors()
         .iter()
         .map(|e| {
-            tracing::warn!("Parse error: {e}");
+            let message = e.to_string();
+            let range = e.range();
+            let span = line_index.span(range);
+            tracing::warn!(
+                "Parse error at {}:{}: {message}",
+                span.start.line,
+                span.start.column
+            );
             ParseError {
-                message: e.to_string(),
+                code: ParseErrorCode::from_message(&message),
+                message,
                 severity: "error".to_string(),
-                location: None, // TODO: Extract location from error
+                location: Some(text_range_to_byte_offsets(range)),
+                span: Some(span),
             }
         })
         .collect();
 
     // Extract file information
     let source_file = parsed.tree();
-    let file_info = extract_file_info(&source_file, code, include_private);
+    let mut impls = HashMap::new();
+    collect_impls(source_file.items(), &mut impls);
+    let ctx = ExtractContext {
+        line_index,
+        include_private: request.include_private,
+        cfg_options: request.cfg.as_deref().map(CfgOptions::from_specs),
+        impls,
+        file_dir: request
+            .file_path
+            .as_deref()
+            .and_then(|path| std::path::Path::new(path).parent())
+            .map(|dir| dir.to_string_lossy().into_owned())
+            .unwrap_or_default(),
+        module_dir: request
+            .file_path
+            .as_deref()
+            .map(file_module_dir)
+            .unwrap_or_default(),
+    };
+    let file_info = extract_file_info(&source_file, &ctx);
 
     Ok(ParseResponse {
         success: errors.is_empty(),
@@ -164,52 +693,87 @@ pub fn parse_rust_code(code: &str, include_private: bool) -> Result<ParseRespons
     })
 }
 
-fn extract_file_info(
-    source_file: &SourceFile,
-    full_source: &str,
+/// Per-file state shared by the extractors.
+struct ExtractContext<'a> {
+    line_index: LineIndex<'a>,
     include_private: bool,
-) -> FileInfo {
+    cfg_options: Option<CfgOptions>,
+    impls: HashMap<String, Vec<ast::Impl>>, // Impl blocks keyed by self type name
+    file_dir: String,                       // Directory of the request's `file_path`
+    module_dir: String,                     // Where child modules live, relative to `file_dir`
+}
+
+impl ExtractContext<'_> {
+    fn span(&self, range: TextRange) -> SourceSpan {
+        self.line_index.span(range)
+    }
+
+    /// The item's cfg predicate and whether it is active.
+    ///
+    /// Several `#[cfg]` attributes on one item must all hold, so they're
+    /// combined into an `all(...)`. Predicates we can't parse, and any
+    /// predicate when no cfg options were given, are treated as active
+    /// rather than hiding the item.
+    fn cfg_status(&self, attributes: &[AttributeInfo]) -> (Option<String>, bool) {
+        let predicates: Vec<&str> = attributes
+            .iter()
+            .filter(|attr| attr.path == "cfg")
+            .flat_map(|attr| attr.args.iter().map(String::as_str))
+            .collect();
+
+        let predicate = match predicates.as_slice() {
+            [] => return (None, true),
+            [predicate] => predicate.to_string(),
+            predicates => format!("all({})", predicates.join(", ")),
+        };
+        let is_active = match &self.cfg_options {
+            Some(options) => CfgExpr::parse(&predicate)
+                .map(|expr| expr.eval(options))
+                .unwrap_or(true),
+            None => true,
+        };
+
+        (Some(predicate), is_active)
+    }
+}
+
+fn extract_file_info(source_file: &SourceFile, ctx: &ExtractContext) -> FileInfo {
     let mut items = Vec::new();
     let mut module_references = Vec::new();
 
     for item in source_file.items() {
         match &item {
             ast::Item::Module(module) => {
-                if should_include_item(module.visibility(), include_private) {
+                if should_include_item(
+                    &extract_visibility(module.visibility()),
+                    module,
+                    ctx.include_private,
+                ) {
                     if let Some(name) = module.name() {
-                        let module_name = name.text().to_string();
-                        let location = text_range_to_byte_offsets(module.syntax().text_range());
-
                         if module.item_list().is_some() {
                             // Inline module: mod foo { ... } - treat as regular item
-                            if let Some(item_info) =
-                                extract_item_info(item.clone(), full_source, include_private)
-                            {
+                            if let Some(item_info) = extract_item_info(item.clone(), ctx) {
                                 items.push(item_info);
                             }
                         } else {
                             // Module reference: mod foo;
-                            let expected_paths = vec![
-                                format!("{}.rs", module_name),
-                                format!("{}/mod.rs", module_name),
-                            ];
-
-                            module_references.push(ModuleReference {
-                                name: module_name,
-                                visibility: extract_visibility(module.visibility()),
-                                expected_paths,
-                                location,
-                            });
+                            module_references.push(extract_module_reference(
+                                module,
+                                name.text().to_string(),
+                                None,
+                                ctx,
+                            ));
                         }
                     }
+                } else {
+                    items.extend(exported_macros(module, ctx));
                 }
             }
             _ => {
                 // Check visibility before including item
-                if should_include_item(get_item_visibility(&item), include_private) {
-                    if let Some(item_info) =
-                        extract_item_info(item.clone(), full_source, include_private)
-                    {
+                if should_include_item(&extract_item_visibility(&item), &item, ctx.include_private)
+                {
+                    if let Some(item_info) = extract_item_info(item.clone(), ctx) {
                         items.push(item_info);
                     }
                 }
@@ -218,67 +782,71 @@ fn extract_file_info(
     }
 
     FileInfo {
+        doc_comment: extract_inner_doc_comment(source_file),
         items,
         module_references,
     }
 }
 
+/// `parent_dir` is where modules declared in the enclosing scope live,
+/// relative to the file's directory.
 fn extract_module_info(
     module: &ast::Module,
-    full_source: &str,
-    include_private: bool,
+    parent_dir: &str,
+    ctx: &ExtractContext,
 ) -> Option<ItemInfo> {
     let name = module.name()?.text().to_string();
-    let syntax = module.syntax();
-    let full_code = syntax.text().to_string();
-    let location = text_range_to_byte_offsets(syntax.text_range());
-    let doc_comment = extract_doc_comment(module);
     let visibility = extract_visibility(module.visibility());
 
     // Only handle inline modules here (mod foo { ... })
     if let Some(item_list) = module.item_list() {
+        let inner_doc_comment = extract_inner_doc_comment(&item_list);
+        // `#[path]` on an inline module renames its directory
+        let dir = join_path(
+            parent_dir,
+            &extract_path_attribute(&extract_attributes(module)).unwrap_or_else(|| name.clone()),
+        );
         let mut items = Vec::new();
         let mut module_references = Vec::new();
 
         for item in item_list.items() {
             match &item {
                 ast::Item::Module(nested_module) => {
-                    if should_include_item(nested_module.visibility(), include_private) {
+                    if should_include_item(
+                        &extract_visibility(nested_module.visibility()),
+                        nested_module,
+                        ctx.include_private,
+                    ) {
                         if let Some(nested_name) = nested_module.name() {
-                            let nested_location =
-                                text_range_to_byte_offsets(nested_module.syntax().text_range());
-
                             if nested_module.item_list().is_some() {
                                 // Nested inline module
                                 if let Some(nested_item) =
-                                    extract_item_info(item.clone(), full_source, include_private)
+                                    extract_module_info(nested_module, &dir, ctx)
                                 {
                                     items.push(nested_item);
                                 }
                             } else {
                                 // Nested module reference
-                                let nested_name_str = nested_name.text().to_string();
-                                let expected_paths = vec![
-                                    format!("{}/{}.rs", name, nested_name_str),
-                                    format!("{}/{}/mod.rs", name, nested_name_str),
-                                ];
-
-                                module_references.push(ModuleReference {
-                                    name: nested_name_str,
-                                    visibility: extract_visibility(nested_module.visibility()),
-                                    expected_paths,
-                                    location: nested_location,
-                                });
+                                module_references.push(extract_module_reference(
+                                    nested_module,
+                                    nested_name.text().to_string(),
+                                    Some(&dir),
+                                    ctx,
+                                ));
                             }
                         }
+                    } else {
+                        items.extend(exported_macros(nested_module, ctx));
                     }
                 }
                 _ => {
                     // Check visibility before including item
-                    if should_include_item(get_item_visibility(&item), include_private) {
-                        if let Some(item_info) =
-                            extract_item_info(item.clone(), full_source, include_private)
-                        {
+                    if should_include_item(
+                        &extract_item_visibility(&item),
+                        &item,
+                        ctx.include_private,
+                    ) {
+                        if let Some(item_info) = extract_item_info(item.clone(), ctx) {
                             items.push(item_info);
                         }
                     }
@@ -286,82 +854,282 @@ fn extract_module_info(
             }
         }
 
-        Some(ItemInfo {
-            name,
-            full_code,
-            doc_comment,
-            visibility,
-            location,
-            details: ItemDetails::Module(ModuleDetails {
-                items,
-                module_references,
-            }),
-        })
+        let details = ItemDetails::Module(ModuleDetails {
+            doc_comment: inner_doc_comment,
+            items,
+            module_references,
+        });
+        Some(item_info(module, name, visibility, details, ctx))
     } else {
         // Module reference (mod foo;) - shouldn't be handled here
         None
     }
 }
 
-fn should_include_item(vis: Option<ast::Visibility>, include_private: bool) -> bool {
-    if include_private {
-        true
-    } else {
-        match vis {
-            Some(v) => v.syntax().text().to_string().contains("pub"),
-            None => false,
+/// `#[macro_export]` macros defined anywhere inside an inline module that
+/// is itself left out.
+///
+/// Exported macros live at the crate root whatever module they're written
+/// in, so they stay part of the public API when their module isn't.
+fn exported_macros(module: &ast::Module, ctx: &ExtractContext) -> Vec<ItemInfo> {
+    let Some(item_list) = module.item_list() else {
+        return Vec::new();
+    };
+
+    let mut macros = Vec::new();
+    for item in item_list.items() {
+        match &item {
+            ast::Item::MacroRules(m) if is_macro_export(m) => {
+                macros.extend(extract_item_info(item.clone(), ctx));
+            }
+            ast::Item::Module(nested_module) => {
+                macros.extend(exported_macros(nested_module, ctx));
+            }
+            _ => {}
         }
     }
-}
 
-fn get_item_visibility(item: &ast::Item) -> Option<ast::Visibility> {
-    match item {
-        ast::Item::Fn(f) => f.visibility(),
-        ast::Item::Struct(s) => s.visibility(),
-        ast::Item::Enum(e) => e.visibility(),
-        ast::Item::Trait(t) => t.visibility(),
-        ast::Item::Module(m) => m.visibility(),
-        ast::Item::Use(u) => u.visibility(),
-        ast::Item::Const(c) => c.visibility(),
-        ast::Item::Static(s) => s.visibility(),
-        ast::Item::TypeAlias(t) => t.visibility(),
-        ast::Item::Impl(_) => None, // impl blocks don't have visibility
-        _ => None,
+    let (_, is_cfg_active) = ctx.cfg_status(&extract_attributes(module));
+    if !is_cfg_active {
+        deactivate_cfg(&mut macros, &mut []);
     }
+    macros
 }
 
-fn extract_item_info(
-    item: ast::Item,
-    full_source: &str,
-    include_private: bool,
-) -> Option<ItemInfo> {
-    match item {
-        ast::Item::Fn(func) => extract_function_info(func, full_source, include_private),
-        ast::Item::Struct(_) => {
-            extract_adt_info(item.clone(), "struct", full_source, include_private)
+/// A `mod name;` declaration and the files its module may be loaded from.
+///
+/// `dir` is the directory of the enclosing inline module relative to the
+/// file's directory, or `None` at the top level of the file. There a
+/// `#[path]` attribute is relative to the file's own directory, while other
+/// modules follow the 2018 layout (see [`file_module_dir`]). A `#[path]`
+/// attribute is the only candidate when present.
+fn extract_module_reference(
+    module: &ast::Module,
+    name: String,
+    dir: Option<&str>,
+    ctx: &ExtractContext,
+) -> ModuleReference {
+    let attributes = extract_attributes(module);
+    let path_attribute = extract_path_attribute(&attributes);
+    let expected_paths = match &path_attribute {
+        Some(path) => vec![join_path(dir.unwrap_or_default(), path)],
+        None => {
+            let dir = dir.unwrap_or(&ctx.module_dir);
+            vec![
+                join_path(dir, &format!("{name}.rs")),
+                join_path(dir, &format!("{name}/mod.rs")),
+            ]
         }
-        ast::Item::Enum(_) => extract_adt_info(item.clone(), "enum", full_source, include_private),
-        ast::Item::Union(_) => {
-            extract_adt_info(item.clone(), "union", full_source, include_private)
+    };
+    let expected_paths = expected_paths
+        .iter()
+        .map(|path| normalize_path(&join_path(&ctx.file_dir, path)))
+        .collect();
+    let (cfg, is_cfg_active) = ctx.cfg_status(&attributes);
+    let range = module.syntax().text_range();
+
+    ModuleReference {
+        name,
+        is_public_api: is_public_module(module),
+        visibility: extract_visibility(module.visibility()),
+        expected_paths,
+        path_attribute,
+        cfg,
+        is_cfg_active,
+        location: text_range_to_byte_offsets(range),
+        span: ctx.span(range),
+    }
+}
+
+/// Joins a relative path onto a directory; absolute paths replace it.
+fn join_path(dir: &str, path: &str) -> String {
+    if dir.is_empty() || path.starts_with('/') {
+        path.to_string()
+    } else {
+        format!("{dir}/{path}")
+    }
+}
+
+/// Resolves `.` and `..` segments, which `#[path]` attributes may contain.
+fn normalize_path(path: &str) -> String {
+    let mut segments: Vec<&str> = Vec::new();
+    for segment in path.split('/') {
+        match segment {
+            "." => {}
+            // `..` stays at the root of an absolute path
+            ".." if segments == [""] => {}
+            // `..` can't climb above the start of a relative path
+            ".." if segments
+                .last()
+                .is_some_and(|last| !last.is_empty() && *last != "..") =>
+            {
+                segments.pop();
+            }
+            "" if !segments.is_empty() => {}
+            segment => segments.push(segment),
         }
-        ast::Item::Trait(t) => extract_trait_info(t, full_source, include_private),
-        ast::Item::Module(m) => extract_module_info(&m, full_source, include_private),
-        other => extract_other_item_info(other, full_source, include_private),
     }
+    segments.join("/")
 }
 
-fn extract_function_info(
-    func: ast::Fn,
-    _full_source: &str,
-    _include_private: bool,
-) -> Option<ItemInfo> {
-    let name = func.name()?.text().to_string();
-    let syntax = func.syntax();
-    let full_code = syntax.text().to_string();
-    let location = text_range_to_byte_offsets(syntax.text_range());
-    let doc_comment = extract_doc_comment(&func);
+/// The directory a file's child modules live in, relative to the file's
+/// own directory.
+///
+/// Child modules of `lib.rs`, `main.rs` and `mod.rs` sit next to them; those
+/// of any other file `foo.rs` go in `foo/`. Crate roots are only recognised
+/// by those names, so `src/bin/foo.rs`, `examples/foo.rs` or `build.rs` are
+/// treated as modules and get a `foo/` (or `build/`) directory too.
+fn file_module_dir(file_path: &str) -> String {
+    let path = std::path::Path::new(file_path);
+    match path.file_name().and_then(|name| name.to_str()) {
+        Some("mod.rs" | "lib.rs" | "main.rs") | None => String::new(),
+        Some(_) => path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default(),
+    }
+}
 
-    // Extract function signature (everything before the body)
+fn deactivate_cfg(items: &mut [ItemInfo], module_references: &mut [ModuleReference]) {
+    for reference in module_references {
+        reference.is_cfg_active = false;
+    }
+    for item in items {
+        item.is_cfg_active = false;
+        match &mut item.details {
+            ItemDetails::Module(module) => {
+                deactivate_cfg(&mut module.items, &mut module.module_references)
+            }
+            ItemDetails::Adt(adt) => deactivate_cfg(&mut adt.methods, &mut []),
+            _ => {}
+        }
+    }
+}
+
+fn hide_from_public_api(items: &mut [ItemInfo], module_references: &mut [ModuleReference]) {
+    for reference in module_references {
+        reference.is_public_api = false;
+    }
+    for item in items {
+        // Exported macros live at the crate root, wherever they're defined
+        if matches!(&item.details, ItemDetails::Macro(details) if details.is_exported) {
+            continue;
+        }
+        item.is_public_api = false;
+        match &mut item.details {
+            ItemDetails::Module(module) => {
+                hide_from_public_api(&mut module.items, &mut module.module_references)
+            }
+            ItemDetails::Adt(adt) => hide_from_public_api(&mut adt.methods, &mut []),
+            _ => {}
+        }
+    }
+}
+
+/// Only a bare `pub` is visible outside the crate, and `#[doc(hidden)]`
+/// items are deliberately left out of the documented API.
+///
+/// This only looks at the item itself; callers clear the flag for items
+/// whose enclosing module or type isn't public.
+fn is_public_api(visibility: &Visibility, known_attributes: &KnownAttributes) -> bool {
+    *visibility == Visibility::Public && !known_attributes.is_doc_hidden
+}
+
+fn is_public_module(module: &ast::Module) -> bool {
+    is_public_api(
+        &extract_visibility(module.visibility()),
+        &KnownAttributes::from_attributes(&extract_attributes(module)),
+    )
+}
+
+fn is_doc_hidden<N: AstNode>(node: &N) -> bool {
+    KnownAttributes::from_attributes(&extract_attributes(node)).is_doc_hidden
+}
+
+/// Whether to extract an item at all: everything when `include_private` is
+/// set, otherwise only the item's own public API check.
+fn should_include_item<N: AstNode>(
+    visibility: &Visibility,
+    node: &N,
+    include_private: bool,
+) -> bool {
+    include_private
+        || is_public_api(
+            visibility,
+            &KnownAttributes::from_attributes(&extract_attributes(node)),
+        )
+}
+
+fn extract_item_info(item: ast::Item, ctx: &ExtractContext) -> Option<ItemInfo> {
+    match item {
+        ast::Item::Fn(func) if is_proc_macro(&func) => extract_proc_macro_info(func, ctx),
+        ast::Item::Fn(func) => extract_function_info(func, ctx),
+        ast::Item::Struct(_) => extract_adt_info(item.clone(), "struct", ctx),
+        ast::Item::Enum(_) => extract_adt_info(item.clone(), "enum", ctx),
+        ast::Item::Union(_) => extract_adt_info(item.clone(), "union", ctx),
+        ast::Item::Trait(t) => extract_trait_info(t, ctx),
+        ast::Item::Impl(i) => extract_impl_info(i, ctx),
+        ast::Item::TypeAlias(t) => extract_type_alias_info(t, ctx),
+        ast::Item::MacroRules(_) | ast::Item::MacroDef(_) => extract_macro_info(item, ctx),
+        ast::Item::ExternBlock(block) => extract_extern_block_info(block, ctx),
+        ast::Item::ExternCrate(extern_crate) => extract_extern_crate_info(extern_crate, ctx),
+        ast::Item::Use(u) => extract_use_info(u, ctx),
+        ast::Item::Module(m) => extract_module_info(&m, &ctx.module_dir, ctx),
+        other => extract_other_item_info(other, ctx),
+    }
+}
+
+/// Builds an item from its kind-specific `details`, reading the fields every
+/// item shares (source, docs, attributes, cfg) off `node`.
+///
+/// Anything nested in the details of an item that's cfg'd out or not part of
+/// the public API is marked the same way.
+fn item_info<N: AstNode>(
+    node: &N,
+    name: String,
+    visibility: Visibility,
+    details: ItemDetails,
+    ctx: &ExtractContext,
+) -> ItemInfo {
+    let syntax = node.syntax();
+    let doc_comment = extract_doc_comment(node);
+    let attributes = extract_attributes(node);
+    let (cfg, is_cfg_active) = ctx.cfg_status(&attributes);
+    let known_attributes = KnownAttributes::from_attributes(&attributes);
+
+    let mut info = ItemInfo {
+        name,
+        full_code: syntax.text().to_string(),
+        doc_sections: doc_comment.as_deref().map(parse_doc_sections),
+        doctests: extract_doctests(node, &ctx.line_index),
+        doc_comment,
+        is_public_api: is_public_api(&visibility, &known_attributes),
+        visibility,
+        known_attributes,
+        cfg,
+        is_cfg_active,
+        attributes,
+        location: text_range_to_byte_offsets(syntax.text_range()),
+        span: ctx.span(syntax.text_range()),
+        details,
+    };
+
+    if !info.is_cfg_active {
+        deactivate_cfg(std::slice::from_mut(&mut info), &mut []);
+    }
+    // `pub` items inside a private module or type can't be named from outside the crate
+    if !info.is_public_api {
+        hide_from_public_api(std::slice::from_mut(&mut info), &mut []);
+    }
+    info
+}
+
+fn extract_function_info(func: ast::Fn, ctx: &ExtractContext) -> Option<ItemInfo> {
+    let name = func.name()?.text().to_string();
+    let syntax = func.syntax();
+    let full_code = syntax.text().to_string();
+
+    // Extract function signature (everything before the body)
     let signature = if let Some(body) = func.body() {
         let body_start = body.syntax().text_range().start();
         let func_start = syntax.text_range().start();
@@ -372,102 +1140,673 @@ fn extract_function_info(
         full_code.clone()
     };
 
-    Some(ItemInfo {
-        name,
-        full_code,
-        doc_comment,
-        visibility: extract_visibility(func.visibility()),
-        location,
-        details: ItemDetails::Function(FunctionDetails { signature }),
-    })
+    let visibility = extract_visibility(func.visibility());
+    let details = ItemDetails::Function(extract_function_details(&func, signature));
+    Some(item_info(&func, name, visibility, details, ctx))
 }
 
-fn extract_adt_info(
-    item: ast::Item,
-    adt_type: &str,
-    full_source: &str,
-    include_private: bool,
-) -> Option<ItemInfo> {
-    let (name, syntax, doc_comment, visibility) = match &item {
+fn extract_function_details(func: &ast::Fn, signature: String) -> FunctionDetails {
+    let param_list = func.param_list();
+    let receiver = param_list
+        .as_ref()
+        .and_then(|params| params.self_param())
+        .map(|self_param| extract_receiver(&self_param));
+    let params = param_list
+        .iter()
+        .flat_map(|params| params.params())
+        .map(|param| FunctionParam {
+            pattern: param.pat().map(|pat| node_text(&pat)),
+            ty: param.ty().map(|ty| node_text(&ty)),
+        })
+        .collect();
+
+    FunctionDetails {
+        signature,
+        is_async: func.async_token().is_some(),
+        is_const: func.const_token().is_some(),
+        is_unsafe: func.unsafe_token().is_some(),
+        abi: func.abi().map(|abi| extract_abi(&abi)),
+        receiver,
+        params,
+        return_type: func
+            .ret_type()
+            .and_then(|ret| ret.ty())
+            .map(|ty| node_text(&ty)),
+        generics: extract_generics(func),
+        impl_origin: None,
+    }
+}
+
+fn extract_receiver(self_param: &ast::SelfParam) -> Receiver {
+    if let Some(ty) = self_param.ty() {
+        Receiver::Typed(node_text(&ty))
+    } else if self_param.amp_token().is_none() {
+        Receiver::Value
+    } else if self_param.mut_token().is_some() {
+        Receiver::RefMut
+    } else {
+        Receiver::Ref
+    }
+}
+
+fn extract_abi(abi: &ast::Abi) -> String {
+    // `extern` on its own means the C ABI
+    let name = node_text(abi)
+        .trim_start_matches("extern")
+        .trim()
+        .trim_matches('"')
+        .to_string();
+    if name.is_empty() {
+        "C".to_string()
+    } else {
+        name
+    }
+}
+
+fn extract_generics<T: HasGenericParams>(node: &T) -> Generics {
+    let params = node
+        .generic_param_list()
+        .iter()
+        .flat_map(|list| list.generic_params())
+        .filter_map(|param| match param {
+            ast::GenericParam::TypeParam(p) => Some(GenericParam {
+                name: p.name()?.text().to_string(),
+                kind: GenericParamKind::Type,
+                bounds: extract_bounds(p.type_bound_list()),
+                ty: None,
+                default: p.default_type().map(|ty| node_text(&ty)),
+            }),
+            ast::GenericParam::LifetimeParam(p) => Some(GenericParam {
+                name: node_text(&p.lifetime()?),
+                kind: GenericParamKind::Lifetime,
+                bounds: extract_bounds(p.type_bound_list()),
+                ty: None,
+                default: None,
+            }),
+            ast::GenericParam::ConstParam(p) => Some(GenericParam {
+                name: p.name()?.text().to_string(),
+                kind: GenericParamKind::Const,
+                bounds: Vec::new(),
+                ty: p.ty().map(|ty| node_text(&ty)),
+                default: p.default_val().map(|val| node_text(&val)),
+            }),
+        })
+        .collect();
+
+    let where_predicates = node
+        .where_clause()
+        .iter()
+        .flat_map(|clause| clause.predicates())
+        .map(|pred| {
+            let pred_text = node_text(&pred);
+            // Everything before the bound list is the bounded type or lifetime
+            let target = match pred.type_bound_list() {
+                Some(bounds) => {
+                    let bounds_start: usize = (bounds.syntax().text_range().start()
+                        - pred.syntax().text_range().start())
+                    .into();
+                    pred_text[..bounds_start].trim_end().trim_end_matches(':')
+                }
+                None => pred_text.trim_end_matches(':'),
+            };
+            WherePredicate {
+                target: target.trim().to_string(),
+                bounds: extract_bounds(pred.type_bound_list()),
+            }
+        })
+        .collect();
+
+    Generics {
+        params,
+        where_predicates,
+    }
+}
+
+fn extract_bounds(bound_list: Option<ast::TypeBoundList>) -> Vec<String> {
+    bound_list
+        .iter()
+        .flat_map(|list| list.bounds())
+        .map(|bound| node_text(&bound))
+        .collect()
+}
+
+fn extract_adt_info(item: ast::Item, adt_type: &str, ctx: &ExtractContext) -> Option<ItemInfo> {
+    let (name, visibility) = match &item {
         ast::Item::Struct(s) => (
             s.name()?.text().to_string(),
-            s.syntax(),
-            extract_doc_comment(s),
             extract_visibility(s.visibility()),
         ),
         ast::Item::Enum(e) => (
             e.name()?.text().to_string(),
-            e.syntax(),
-            extract_doc_comment(e),
             extract_visibility(e.visibility()),
         ),
         ast::Item::Union(u) => (
             u.name()?.text().to_string(),
-            u.syntax(),
-            extract_doc_comment(u),
             extract_visibility(u.visibility()),
         ),
         _ => return None,
     };
 
-    let full_code = syntax.text().to_string();
-    let location = text_range_to_byte_offsets(syntax.text_range());
+    let (field_kind, fields) = match &item {
+        ast::Item::Struct(s) => match s.field_list() {
+            Some(field_list) => (
+                Some(field_list_kind(&field_list)),
+                extract_fields(&field_list, ctx),
+            ),
+            None => (Some(FieldListKind::Unit), Vec::new()),
+        },
+        ast::Item::Union(u) => (
+            Some(FieldListKind::Named),
+            u.record_field_list()
+                .map(|fields| {
+                    let field_list = ast::FieldList::RecordFieldList(fields);
+                    extract_fields(&field_list, ctx)
+                })
+                .unwrap_or_default(),
+        ),
+        _ => (None, Vec::new()),
+    };
+    let variants = match &item {
+        ast::Item::Enum(e) => extract_variants(e, ctx),
+        _ => Vec::new(),
+    };
+    let generics = match &item {
+        ast::Item::Struct(s) => extract_generics(s),
+        ast::Item::Enum(e) => extract_generics(e),
+        ast::Item::Union(u) => extract_generics(u),
+        _ => Generics::default(),
+    };
 
     // Find impl blocks for this ADT in the source file
-    let methods = extract_adt_methods(&name, full_source, include_private);
+    let (methods, trait_impls) = extract_adt_impls(&name, ctx);
+
+    let details = ItemDetails::Adt(AdtDetails {
+        adt_type: adt_type.to_string(),
+        generics,
+        field_kind,
+        fields,
+        variants,
+        trait_impls,
+        methods,
+    });
+    Some(item_info(&item, name, visibility, details, ctx))
+}
 
-    Some(ItemInfo {
-        name,
-        full_code,
-        doc_comment,
-        visibility,
-        location,
-        details: ItemDetails::Adt(AdtDetails {
-            adt_type: adt_type.to_string(),
-            methods,
-        }),
-    })
+fn field_list_kind(field_list: &ast::FieldList) -> FieldListKind {
+    match field_list {
+        ast::FieldList::RecordFieldList(_) => FieldListKind::Named,
+        ast::FieldList::TupleFieldList(_) => FieldListKind::Tuple,
+    }
 }
 
-fn extract_other_item_info(
-    item: ast::Item,
-    _full_source: &str,
-    _include_private: bool,
-) -> Option<ItemInfo> {
-    let syntax = item.syntax();
-    let full_code = syntax.text().to_string();
-    let location = text_range_to_byte_offsets(syntax.text_range());
-    let doc_comment = None; // Other items don't implement HasDocComments uniformly
+/// Fields of a struct or union, leaving out private ones unless
+/// `ctx.include_private` is set.
+fn extract_fields(field_list: &ast::FieldList, ctx: &ExtractContext) -> Vec<FieldInfo> {
+    let mut fields = extract_field_list(field_list, false, ctx);
+    if !ctx.include_private {
+        fields.retain(|field| {
+            is_public_api(
+                &field.visibility,
+                &KnownAttributes::from_attributes(&field.attributes),
+            )
+        });
+    }
+    fields
+}
+
+/// Fields of an enum variant, which are always as visible as the enum itself.
+fn extract_variant_fields(field_list: &ast::FieldList, ctx: &ExtractContext) -> Vec<FieldInfo> {
+    extract_field_list(field_list, true, ctx)
+}
 
+/// `in_variant` fields take the enum's visibility rather than declaring
+/// their own.
+fn extract_field_list(
+    field_list: &ast::FieldList,
+    in_variant: bool,
+    ctx: &ExtractContext,
+) -> Vec<FieldInfo> {
+    let visibility = |vis: Option<ast::Visibility>| {
+        if in_variant {
+            Visibility::Inherited
+        } else {
+            extract_visibility(vis)
+        }
+    };
+    let mut fields = Vec::new();
+
+    match field_list {
+        ast::FieldList::RecordFieldList(list) => {
+            for field in list.fields() {
+                let Some(name) = field.name() else {
+                    continue;
+                };
+                let syntax = field.syntax();
+                fields.push(FieldInfo {
+                    name: name.text().to_string(),
+                    ty: field.ty().map(|ty| node_text(&ty)).unwrap_or_default(),
+                    visibility: visibility(field.visibility()),
+                    doc_comment: extract_doc_comment(&field),
+                    attributes: extract_attributes(&field),
+                    location: text_range_to_byte_offsets(syntax.text_range()),
+                    span: ctx.span(syntax.text_range()),
+                });
+            }
+        }
+        ast::FieldList::TupleFieldList(list) => {
+            // Indices count every field, including private ones filtered out later
+            for (index, field) in list.fields().enumerate() {
+                let syntax = field.syntax();
+                fields.push(FieldInfo {
+                    name: index.to_string(),
+                    ty: field.ty().map(|ty| node_text(&ty)).unwrap_or_default(),
+                    visibility: visibility(field.visibility()),
+                    doc_comment: extract_doc_comment(&field),
+                    attributes: extract_attributes(&field),
+                    location: text_range_to_byte_offsets(syntax.text_range()),
+                    span: ctx.span(syntax.text_range()),
+                });
+            }
+        }
+    }
+
+    fields
+}
+
+fn extract_variants(enum_item: &ast::Enum, ctx: &ExtractContext) -> Vec<VariantInfo> {
+    let mut variants = Vec::new();
+
+    for variant in enum_item
+        .variant_list()
+        .iter()
+        .flat_map(|list| list.variants())
+    {
+        let Some(name) = variant.name() else {
+            continue;
+        };
+        let syntax = variant.syntax();
+        let field_list = variant.field_list();
+
+        variants.push(VariantInfo {
+            name: name.text().to_string(),
+            kind: field_list
+                .as_ref()
+                .map(field_list_kind)
+                .unwrap_or(FieldListKind::Unit),
+            fields: field_list
+                .map(|fields| extract_variant_fields(&fields, ctx))
+                .unwrap_or_default(),
+            discriminant: variant.expr().map(|expr| node_text(&expr)),
+            doc_comment: extract_doc_comment(&variant),
+            attributes: extract_attributes(&variant),
+            location: text_range_to_byte_offsets(syntax.text_range()),
+            span: ctx.span(syntax.text_range()),
+        });
+    }
+
+    variants
+}
+
+fn extract_other_item_info(item: ast::Item, ctx: &ExtractContext) -> Option<ItemInfo> {
     let (name, item_type) = match &item {
         // Enums are now handled as ADTs
-        ast::Item::Use(u) => (u.use_tree()?.syntax().text().to_string(), "use".to_string()),
         ast::Item::Const(c) => (c.name()?.text().to_string(), "const".to_string()),
         ast::Item::Static(s) => (s.name()?.text().to_string(), "static".to_string()),
-        ast::Item::TypeAlias(t) => (t.name()?.text().to_string(), "type_alias".to_string()),
-        ast::Item::Impl(i) => {
-            let impl_type = i.self_ty()?.syntax().text().to_string();
-            let trait_name = i.trait_().map(|t| t.syntax().text().to_string());
-            let name = trait_name
-                .as_ref()
-                .map(|t| format!("{t} for {impl_type}"))
-                .unwrap_or(impl_type);
-            (name, "impl".to_string())
-        }
         _ => ("unknown".to_string(), "unknown".to_string()),
     };
 
-    Some(ItemInfo {
+    let visibility = extract_item_visibility(&item);
+    let details = ItemDetails::Other(OtherDetails { item_type });
+    Some(item_info(&item, name, visibility, details, ctx))
+}
+
+fn extract_macro_info(item: ast::Item, ctx: &ExtractContext) -> Option<ItemInfo> {
+    let visibility = extract_item_visibility(&item);
+
+    let (name, details) = match &item {
+        ast::Item::MacroRules(m) => {
+            let name = m.name()?.text().to_string();
+            let rules = m
+                .token_tree()
+                .map(|body| extract_macro_rules(&name, &body, ctx))
+                .unwrap_or_default();
+            let details = MacroDetails {
+                kind: MacroKind::MacroRules,
+                is_exported: is_macro_export(m),
+                rules,
+                helper_attributes: Vec::new(),
+                function_name: None,
+            };
+            (name, details)
+        }
+        ast::Item::MacroDef(m) => {
+            let name = m.name()?.text().to_string();
+            // `macro foo($x:expr) { ... }` is a single rule; without arguments
+            // the body holds the rules, as in `macro_rules!`
+            let rules = match (m.args(), m.body()) {
+                (Some(args), Some(body)) => {
+                    let range = args.syntax().text_range().cover(body.syntax().text_range());
+                    vec![macro_rule(&name, &args, range, ctx)]
+                }
+                (None, Some(body)) => extract_macro_rules(&name, &body, ctx),
+                _ => Vec::new(),
+            };
+            let details = MacroDetails {
+                kind: MacroKind::DeclMacro,
+                is_exported: visibility == Visibility::Public,
+                rules,
+                helper_attributes: Vec::new(),
+                function_name: None,
+            };
+            (name, details)
+        }
+        _ => return None,
+    };
+
+    Some(item_info(
+        &item,
         name,
-        full_code,
+        visibility,
+        ItemDetails::Macro(details),
+        ctx,
+    ))
+}
+
+/// A proc macro entry point, reported under the name it's invoked by rather
+/// than the name of the function implementing it.
+fn extract_proc_macro_info(func: ast::Fn, ctx: &ExtractContext) -> Option<ItemInfo> {
+    let function_name = func.name()?.text().to_string();
+    let attributes = extract_attributes(&func);
+
+    let (attr, kind) = attributes
+        .iter()
+        .find_map(|attr| Some((attr, proc_macro_kind(attr)?)))?;
+    // Derives are named by their first argument and may declare helpers
+    // as `attributes(foo, bar)`
+    let (name, helper_attributes) = match kind {
+        MacroKind::ProcMacroDerive => {
+            let name = attr.args.first()?.clone();
+            let helper_attributes = attr
+                .args
+                .iter()
+                .filter_map(|arg| arg.strip_prefix("attributes"))
+                .filter_map(|helpers| {
+                    helpers
+                        .trim()
+                        .strip_prefix('(')
+                        .and_then(|helpers| helpers.strip_suffix(')'))
+                })
+                .flat_map(|helpers| helpers.split(','))
+                .map(|helper| helper.trim().to_string())
+                .filter(|helper| !helper.is_empty())
+                .collect();
+            (name, helper_attributes)
+        }
+        _ => (function_name.clone(), Vec::new()),
+    };
+
+    let visibility = extract_visibility(func.visibility());
+    let details = ItemDetails::Macro(MacroDetails {
+        kind,
+        is_exported: true,
+        rules: Vec::new(),
+        helper_attributes,
+        function_name: Some(function_name),
+    });
+    Some(item_info(&func, name, visibility, details, ctx))
+}
+
+fn is_proc_macro(func: &ast::Fn) -> bool {
+    extract_attributes(func)
+        .iter()
+        .any(|attr| proc_macro_kind(attr).is_some())
+}
+
+fn proc_macro_kind(attr: &AttributeInfo) -> Option<MacroKind> {
+    match attr.path.as_str() {
+        "proc_macro" => Some(MacroKind::ProcMacro),
+        "proc_macro_derive" => Some(MacroKind::ProcMacroDerive),
+        "proc_macro_attribute" => Some(MacroKind::ProcMacroAttribute),
+        _ => None,
+    }
+}
+
+/// Splits a macro body into rules.
+///
+/// Rules are `matcher => transcriber` pairs of token trees separated by `;`
+/// (or `,` in 2.0 macros), so the body's direct token tree children simply
+/// alternate between matchers and transcribers.
+fn extract_macro_rules(name: &str, body: &ast::TokenTree, ctx: &ExtractContext) -> Vec<MacroRule> {
+    let trees: Vec<ast::TokenTree> = body
+        .syntax()
+        .children()
+        .filter_map(ast::TokenTree::cast)
+        .collect();
+
+    trees
+        .chunks(2)
+        .map(|rule| {
+            let matcher = &rule[0];
+            let range = match rule.get(1) {
+                Some(transcriber) => matcher
+                    .syntax()
+                    .text_range()
+                    .cover(transcriber.syntax().text_range()),
+                None => matcher.syntax().text_range(),
+            };
+            macro_rule(name, matcher, range, ctx)
+        })
+        .collect()
+}
+
+fn macro_rule(
+    name: &str,
+    matcher: &ast::TokenTree,
+    range: TextRange,
+    ctx: &ExtractContext,
+) -> MacroRule {
+    let matcher = node_text(matcher);
+    MacroRule {
+        call_syntax: format!("{name}!{matcher}"),
+        matcher,
+        location: text_range_to_byte_offsets(range),
+        span: ctx.span(range),
+    }
+}
+
+fn extract_extern_block_info(block: ast::ExternBlock, ctx: &ExtractContext) -> Option<ItemInfo> {
+    let abi = block
+        .abi()
+        .map(|abi| extract_abi(&abi))
+        .unwrap_or_else(|| "C".to_string());
+    let items = foreign_items(&block)
+        .filter_map(|item| extract_foreign_item(item, ctx))
+        .collect();
+
+    let links = extract_links(&extract_attributes(&block));
+
+    let name = format!("extern \"{abi}\"");
+    let visibility = extern_block_visibility(&block);
+    let details = ItemDetails::ExternBlock(ExternBlockDetails {
+        abi,
+        is_unsafe: block.unsafe_token().is_some(),
+        links,
+        items,
+    });
+    Some(item_info(&block, name, visibility, details, ctx))
+}
+
+fn foreign_items(block: &ast::ExternBlock) -> impl Iterator<Item = ast::ExternItem> {
+    block
+        .extern_item_list()
+        .into_iter()
+        .flat_map(|list| list.extern_items())
+}
+
+fn extract_foreign_item(item: ast::ExternItem, ctx: &ExtractContext) -> Option<ForeignItemInfo> {
+    let (name, kind, vis, doc_comment) = match &item {
+        ast::ExternItem::Fn(f) => (
+            f.name()?,
+            ForeignItemKind::Fn,
+            f.visibility(),
+            extract_doc_comment(f),
+        ),
+        ast::ExternItem::Static(s) => (
+            s.name()?,
+            ForeignItemKind::Static,
+            s.visibility(),
+            extract_doc_comment(s),
+        ),
+        ast::ExternItem::TypeAlias(t) => (
+            t.name()?,
+            ForeignItemKind::Type,
+            t.visibility(),
+            extract_doc_comment(t),
+        ),
+        ast::ExternItem::MacroCall(_) => return None,
+    };
+    let visibility = extract_visibility(vis);
+    if !should_include_item(&visibility, &item, ctx.include_private) {
+        return None;
+    }
+
+    let syntax = item.syntax();
+    let link_name = extract_attributes(&item)
+        .into_iter()
+        .find(|attr| attr.path == "link_name")
+        .and_then(|attr| attr.value)
+        .map(|value| unquote(&value));
+
+    Some(ForeignItemInfo {
+        name: name.text().to_string(),
+        kind,
+        signature: text_without_attributes(&item),
+        visibility,
+        link_name,
         doc_comment,
-        visibility: extract_item_visibility(&item),
-        location,
-        details: ItemDetails::Other(OtherDetails { item_type }),
+        location: text_range_to_byte_offsets(syntax.text_range()),
+        span: ctx.span(syntax.text_range()),
     })
 }
 
-fn extract_trait_methods(trait_item: &ast::Trait, _full_source: &str) -> Vec<TraitMethodInfo> {
+fn extract_extern_crate_info(
+    extern_crate: ast::ExternCrate,
+    ctx: &ExtractContext,
+) -> Option<ItemInfo> {
+    let crate_name = extern_crate.name_ref()?.text().to_string();
+    let alias = extern_crate.rename().and_then(rename_alias);
+
+    let name = alias.clone().unwrap_or_else(|| crate_name.clone());
+    let visibility = extract_visibility(extern_crate.visibility());
+    let details = ItemDetails::ExternCrate(ExternCrateDetails { crate_name, alias });
+    Some(item_info(&extern_crate, name, visibility, details, ctx))
+}
+
+/// The name an `as` clause binds, which may be `_`.
+fn rename_alias(rename: ast::Rename) -> Option<String> {
+    match (rename.name(), rename.underscore_token()) {
+        (Some(name), _) => Some(name.text().to_string()),
+        (None, Some(_)) => Some("_".to_string()),
+        (None, None) => None,
+    }
+}
+
+fn extract_use_info(u: ast::Use, ctx: &ExtractContext) -> Option<ItemInfo> {
+    let use_tree = u.use_tree()?;
+    let visibility = extract_visibility(u.visibility());
+
+    let mut imports = Vec::new();
+    let is_reexport = visibility == Visibility::Public;
+    flatten_use_tree(&use_tree, &[], is_reexport, &mut imports);
+
+    let name = use_tree.syntax().text().to_string();
+    let details = ItemDetails::Use(UseDetails { imports });
+    Some(item_info(&u, name, visibility, details, ctx))
+}
+
+/// Expands a use tree into one import per leaf, e.g. `a::{b, c::*}` into
+/// `a::b` and the glob `a::c::*`.
+fn flatten_use_tree(
+    tree: &ast::UseTree,
+    prefix: &[String],
+    is_reexport: bool,
+    imports: &mut Vec<ImportInfo>,
+) {
+    let mut segments = prefix.to_vec();
+    if let Some(path) = tree.path() {
+        // A leading `::` shows up as an empty first segment, keeping it in `path`
+        segments.extend(
+            path.syntax()
+                .text()
+                .to_string()
+                .split("::")
+                .map(|segment| segment.trim().to_string()),
+        );
+    }
+
+    if let Some(list) = tree.use_tree_list() {
+        for nested in list.use_trees() {
+            flatten_use_tree(&nested, &segments, is_reexport, imports);
+        }
+        return;
+    }
+
+    if tree.star_token().is_some() {
+        imports.push(ImportInfo {
+            path: segments.join("::"),
+            name: "*".to_string(),
+            alias: None,
+            is_glob: true,
+            is_reexport,
+        });
+        return;
+    }
+
+    // `use foo::{self};` imports the module `foo` itself
+    if segments.len() > 1 && segments.last().is_some_and(|segment| segment == "self") {
+        segments.pop();
+    }
+    let Some(name) = segments.last().cloned() else {
+        return;
+    };
+    imports.push(ImportInfo {
+        path: segments.join("::"),
+        name,
+        alias: tree.rename().and_then(rename_alias),
+        is_glob: false,
+        is_reexport,
+    });
+}
+
+fn extract_impl_info(i: ast::Impl, ctx: &ExtractContext) -> Option<ItemInfo> {
+    let self_type = i.self_ty()?.syntax().text().to_string();
+    let trait_path = i.trait_().map(|t| t.syntax().text().to_string());
+    let name = trait_path
+        .as_ref()
+        .map(|t| format!("{t} for {self_type}"))
+        .unwrap_or_else(|| self_type.clone());
+
+    let details = ItemDetails::Impl(ImplDetails {
+        self_type,
+        trait_path,
+        generics: extract_generics(&i),
+        is_negative: i.excl_token().is_some(),
+        is_unsafe: i.unsafe_token().is_some(),
+    });
+    Some(item_info(&i, name, Visibility::Inherited, details, ctx))
+}
+
+fn extract_type_alias_info(t: ast::TypeAlias, ctx: &ExtractContext) -> Option<ItemInfo> {
+    let name = t.name()?.text().to_string();
+    let visibility = extract_visibility(t.visibility());
+    let details = ItemDetails::TypeAlias(TypeAliasDetails {
+        aliased_type: t.ty().map(|ty| node_text(&ty)),
+        generics: extract_generics(&t),
+    });
+    Some(item_info(&t, name, visibility, details, ctx))
+}
+
+fn extract_trait_methods(trait_item: &ast::Trait, ctx: &ExtractContext) -> Vec<TraitMethodInfo> {
     let mut methods = Vec::new();
 
     if let Some(assoc_item_list) = trait_item.assoc_item_list() {
@@ -476,6 +1815,7 @@ fn extract_trait_methods(trait_item: &ast::Trait, _full_source: &str) -> Vec<Tra
                 if let Some(name) = func.name() {
                     let syntax = func.syntax();
                     let location = text_range_to_byte_offsets(syntax.text_range());
+                    let span = ctx.span(syntax.text_range());
                     let doc_comment = extract_doc_comment(&func);
 
                     // Extract just the signature (everything before the body if it exists)
@@ -492,8 +1832,10 @@ fn extract_trait_methods(trait_item: &ast::Trait, _full_source: &str) -> Vec<Tra
                     methods.push(TraitMethodInfo {
                         name: name.text().to_string(),
                         signature,
+                        is_required: func.body().is_none(),
                         doc_comment,
                         location,
+                        span,
                     });
                 }
             }
@@ -503,30 +1845,111 @@ fn extract_trait_methods(trait_item: &ast::Trait, _full_source: &str) -> Vec<Tra
     methods
 }
 
-fn extract_adt_methods(adt_name: &str, full_source: &str, include_private: bool) -> Vec<ItemInfo> {
+fn extract_trait_assoc_types(trait_item: &ast::Trait, ctx: &ExtractContext) -> Vec<AssocTypeInfo> {
+    let mut types = Vec::new();
+
+    if let Some(assoc_item_list) = trait_item.assoc_item_list() {
+        for item in assoc_item_list.assoc_items() {
+            if let ast::AssocItem::TypeAlias(type_alias) = item {
+                if let Some(name) = type_alias.name() {
+                    let syntax = type_alias.syntax();
+                    types.push(AssocTypeInfo {
+                        name: name.text().to_string(),
+                        generics: extract_generics(&type_alias),
+                        bounds: extract_bounds(type_alias.type_bound_list()),
+                        default: type_alias.ty().map(|ty| node_text(&ty)),
+                        doc_comment: extract_doc_comment(&type_alias),
+                        location: text_range_to_byte_offsets(syntax.text_range()),
+                        span: ctx.span(syntax.text_range()),
+                    });
+                }
+            }
+        }
+    }
+
+    types
+}
+
+fn extract_trait_assoc_consts(
+    trait_item: &ast::Trait,
+    ctx: &ExtractContext,
+) -> Vec<AssocConstInfo> {
+    let mut consts = Vec::new();
+
+    if let Some(assoc_item_list) = trait_item.assoc_item_list() {
+        for item in assoc_item_list.assoc_items() {
+            if let ast::AssocItem::Const(konst) = item {
+                if let Some(name) = konst.name() {
+                    let syntax = konst.syntax();
+                    consts.push(AssocConstInfo {
+                        name: name.text().to_string(),
+                        ty: konst.ty().map(|ty| node_text(&ty)),
+                        default: konst.body().map(|body| node_text(&body)),
+                        doc_comment: extract_doc_comment(&konst),
+                        location: text_range_to_byte_offsets(syntax.text_range()),
+                        span: ctx.span(syntax.text_range()),
+                    });
+                }
+            }
+        }
+    }
+
+    consts
+}
+
+fn extract_adt_impls(adt_name: &str, ctx: &ExtractContext) -> (Vec<ItemInfo>, Vec<TraitImplInfo>) {
     let mut methods = Vec::new();
+    let mut trait_impls = Vec::new();
 
-    // Parse the full source to find impl blocks for this struct
-    let parsed = SourceFile::parse(full_source, ra_ap_syntax::Edition::Edition2024);
-    let source_file = parsed.tree();
+    for impl_item in ctx.impls.get(adt_name).into_iter().flatten() {
+        let is_impl_hidden = is_doc_hidden(impl_item);
+        if is_impl_hidden && !ctx.include_private {
+            continue;
+        }
 
-    for item in source_file.items() {
-        if let ast::Item::Impl(impl_item) = item {
-            if let Some(self_ty) = impl_item.self_ty() {
-                let impl_type = self_ty.syntax().text().to_string();
-                // Simple name matching - could be improved for generic types
-                if impl_type.contains(adt_name) {
-                    if let Some(assoc_item_list) = impl_item.assoc_item_list() {
-                        for assoc_item in assoc_item_list.assoc_items() {
-                            if let ast::AssocItem::Fn(func) = assoc_item {
-                                if should_include_item(func.visibility(), include_private) {
-                                    if let Some(func_info) =
-                                        extract_function_info(func, full_source, include_private)
-                                    {
-                                        methods.push(func_info);
-                                    }
-                                }
+        let trait_path = impl_item.trait_().map(|t| node_text(&t));
+        if let Some(trait_path) = &trait_path {
+            let syntax = impl_item.syntax();
+            trait_impls.push(TraitImplInfo {
+                trait_path: trait_path.clone(),
+                generics: extract_generics(impl_item),
+                is_negative: impl_item.excl_token().is_some(),
+                is_unsafe: impl_item.unsafe_token().is_some(),
+                location: text_range_to_byte_offsets(syntax.text_range()),
+                span: ctx.span(syntax.text_range()),
+            });
+        }
+        let origin = match trait_path {
+            Some(trait_path) => ImplOrigin::Trait(trait_path),
+            None => ImplOrigin::Inherent,
+        };
+        // Methods of a cfg'd-out impl block are inactive whatever their own cfg
+        let (_, is_impl_active) = ctx.cfg_status(&extract_attributes(impl_item));
+
+        if let Some(assoc_item_list) = impl_item.assoc_item_list() {
+            for assoc_item in assoc_item_list.assoc_items() {
+                if let ast::AssocItem::Fn(func) = assoc_item {
+                    // Trait impl methods can't declare a visibility, so they're
+                    // kept unless hidden. Whether the trait itself is public isn't
+                    // known here, so methods of a private trait count as public too
+                    let is_included = match &origin {
+                        ImplOrigin::Trait(_) => ctx.include_private || !is_doc_hidden(&func),
+                        ImplOrigin::Inherent => should_include_item(
+                            &extract_visibility(func.visibility()),
+                            &func,
+                            ctx.include_private,
+                        ),
+                    };
+                    if is_included {
+                        if let Some(mut func_info) = extract_method_info(func, origin.clone(), ctx)
+                        {
+                            func_info.is_cfg_active &= is_impl_active;
+                            if matches!(origin, ImplOrigin::Trait(_)) {
+                                func_info.visibility = Visibility::Inherited;
+                                func_info.is_public_api = !func_info.known_attributes.is_doc_hidden;
                             }
+                            func_info.is_public_api &= !is_impl_hidden;
+                            methods.push(func_info);
                         }
                     }
                 }
@@ -534,101 +1957,588 @@ fn extract_adt_methods(adt_name: &str, full_source: &str, include_private: bool)
         }
     }
 
-    methods
+    (methods, trait_impls)
 }
 
-fn extract_item_visibility(item: &ast::Item) -> String {
+fn extract_method_info(
+    func: ast::Fn,
+    origin: ImplOrigin,
+    ctx: &ExtractContext,
+) -> Option<ItemInfo> {
+    let mut info = extract_function_info(func, ctx)?;
+    if let ItemDetails::Function(details) = &mut info.details {
+        details.impl_origin = Some(origin);
+    }
+    Some(info)
+}
+
+/// Groups every impl block in the file by the name of its self type.
+///
+/// Impls inside inline modules count too, since they can implement types
+/// declared anywhere in the file.
+fn collect_impls(
+    items: impl Iterator<Item = ast::Item>,
+    impls: &mut HashMap<String, Vec<ast::Impl>>,
+) {
+    for item in items {
+        match item {
+            ast::Item::Impl(impl_item) => {
+                if let Some(type_name) = impl_item.self_ty().and_then(|ty| self_type_name(&ty)) {
+                    impls.entry(type_name).or_default().push(impl_item);
+                }
+            }
+            ast::Item::Module(module) => {
+                if let Some(item_list) = module.item_list() {
+                    collect_impls(item_list.items(), impls);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Name of the type an impl is for: the final path segment, without generic
+/// arguments, looking through references and parentheses.
+fn self_type_name(ty: &ast::Type) -> Option<String> {
+    match ty {
+        ast::Type::PathType(path_type) => {
+            Some(path_type.path()?.segment()?.name_ref()?.text().to_string())
+        }
+        ast::Type::RefType(ref_type) => self_type_name(&ref_type.ty()?),
+        ast::Type::ParenType(paren_type) => self_type_name(&paren_type.ty()?),
+        _ => None,
+    }
+}
+
+fn extract_item_visibility(item: &ast::Item) -> Visibility {
     let vis = match item {
         ast::Item::Fn(f) => f.visibility(),
         ast::Item::Struct(s) => s.visibility(),
         ast::Item::Enum(e) => e.visibility(),
+        ast::Item::Union(u) => u.visibility(),
         ast::Item::Trait(t) => t.visibility(),
         ast::Item::Module(m) => m.visibility(),
         ast::Item::Use(u) => u.visibility(),
         ast::Item::Const(c) => c.visibility(),
         ast::Item::Static(s) => s.visibility(),
         ast::Item::TypeAlias(t) => t.visibility(),
-        ast::Item::Impl(_) => None, // impl blocks don't have visibility
+        ast::Item::MacroDef(m) => m.visibility(),
+        ast::Item::ExternCrate(e) => e.visibility(),
+        ast::Item::ExternBlock(block) => return extern_block_visibility(block),
+        ast::Item::MacroRules(m) if is_macro_export(m) => return Visibility::Public,
+        ast::Item::Impl(_) => return Visibility::Inherited, // impl blocks don't have visibility
         _ => None,
     };
     extract_visibility(vis)
 }
 
-fn extract_trait_info(
-    t: ast::Trait,
-    _full_source: &str,
-    _include_private: bool,
-) -> Option<ItemInfo> {
-    let name = t.name()?.text().to_string();
-    let syntax = t.syntax();
-    let full_code = syntax.text().to_string();
-    let location = text_range_to_byte_offsets(syntax.text_range());
-    let doc_comment = extract_doc_comment(&t);
-
-    // Extract trait methods
-    let methods = extract_trait_methods(&t, _full_source);
+/// Extern blocks can't be marked `pub` themselves, so a block is public
+/// when any of its foreign items is.
+fn extern_block_visibility(block: &ast::ExternBlock) -> Visibility {
+    let is_public = foreign_items(block).any(|item| {
+        let vis = match &item {
+            ast::ExternItem::Fn(f) => f.visibility(),
+            ast::ExternItem::Static(s) => s.visibility(),
+            ast::ExternItem::TypeAlias(t) => t.visibility(),
+            ast::ExternItem::MacroCall(_) => None,
+        };
+        extract_visibility(vis) == Visibility::Public
+    });
+    if is_public {
+        Visibility::Public
+    } else {
+        Visibility::Private
+    }
+}
 
-    Some(ItemInfo {
-        name,
-        full_code,
-        doc_comment,
-        visibility: extract_visibility(t.visibility()),
-        location,
-        details: ItemDetails::Trait(TraitDetails { methods }),
-    })
+fn is_macro_export(macro_rules: &ast::MacroRules) -> bool {
+    extract_attributes(macro_rules)
+        .iter()
+        .any(|attr| attr.path == "macro_export")
 }
 
-// We no longer extract impl blocks as separate items since they're part of struct methods
+fn extract_trait_info(t: ast::Trait, ctx: &ExtractContext) -> Option<ItemInfo> {
+    let name = t.name()?.text().to_string();
 
-// Removed - modules handled separately in FileInfo
+    // Extract trait methods and associated items
+    let methods = extract_trait_methods(&t, ctx);
+    let associated_types = extract_trait_assoc_types(&t, ctx);
+    let associated_consts = extract_trait_assoc_consts(&t, ctx);
+
+    let visibility = extract_visibility(t.visibility());
+    let details = ItemDetails::Trait(TraitDetails {
+        is_unsafe: t.unsafe_token().is_some(),
+        is_auto: t.auto_token().is_some(),
+        generics: extract_generics(&t),
+        supertraits: extract_bounds(t.type_bound_list()),
+        associated_types,
+        associated_consts,
+        methods,
+    });
+    Some(item_info(&t, name, visibility, details, ctx))
+}
 
-// We no longer extract use statements as they're not in our focus
+// Removed - modules handled separately in FileInfo
 
 // We no longer extract const items as they're not in our focus
 
 // We no longer extract static items as they're not in our focus
 
-// We no longer extract type aliases as they're not in our focus
-
-fn extract_visibility(vis: Option<ast::Visibility>) -> String {
-    match vis {
-        Some(v) => {
-            let text = v.syntax().text().to_string();
-            if text.contains("pub(crate)") {
-                "pub(crate)".to_string()
-            } else if text.contains("pub(super)") {
-                "pub(super)".to_string()
-            } else if text.contains("pub(in") {
-                "pub(in path)".to_string()
-            } else if text.contains("pub") {
-                "pub".to_string()
-            } else {
-                "private".to_string()
-            }
-        }
-        None => "private".to_string(),
+fn extract_visibility(vis: Option<ast::Visibility>) -> Visibility {
+    let Some(vis) = vis else {
+        return Visibility::Private;
+    };
+
+    // `pub(crate)`, `pub(super)` and `pub(self)` are paths too, written without `in`
+    match vis.path().map(|path| node_text(&path)) {
+        None => Visibility::Public,
+        Some(path) => match path.as_str() {
+            "crate" => Visibility::Crate,
+            "super" => Visibility::Super,
+            "self" => Visibility::Private,
+            _ => Visibility::InPath(path),
+        },
     }
 }
 
 // Removed old extraction functions that are no longer needed
 
-// Removed old attribute extraction function
+fn node_text<N: AstNode>(node: &N) -> String {
+    node.syntax().text().to_string()
+}
+
+/// Source of `node` starting after its attributes and doc comments.
+fn text_without_attributes<N: AstNode>(node: &N) -> String {
+    let syntax = node.syntax();
+    let node_start = syntax.text_range().start();
+    let start = syntax
+        .children_with_tokens()
+        .find(|child| {
+            !matches!(
+                child.kind(),
+                SyntaxKind::ATTR | SyntaxKind::COMMENT | SyntaxKind::WHITESPACE
+            )
+        })
+        .map(|child| child.text_range().start())
+        .unwrap_or(node_start);
+    let offset: usize = (start - node_start).into();
+    syntax.text().to_string()[offset..].to_string()
+}
 
 fn text_range_to_byte_offsets(range: TextRange) -> [u32; 2] {
     [range.start().into(), range.end().into()]
 }
 
-fn extract_doc_comment<T: HasDocComments>(node: &T) -> Option<String> {
-    let docs: Vec<String> = node
-        .doc_comments()
-        .map(|comment| comment.text().trim_start_matches("///").trim().to_string())
-        .collect();
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    if docs.is_empty() {
-        None
-    } else {
-        Some(docs.join("\n"))
+    fn parse(code: &str) -> ParseResponse {
+        parse_with(code, true, None)
+    }
+
+    fn parse_with(code: &str, include_private: bool, cfg: Option<&[&str]>) -> ParseResponse {
+        let request = ParseRequest {
+            code: code.to_string(),
+            file_path: None,
+            include_private,
+            cfg: cfg.map(|specs| specs.iter().map(|spec| spec.to_string()).collect()),
+            edition: None,
+            manifest: None,
+        };
+        parse_rust_code(&request).unwrap()
     }
-}
 
-// Removed extract_attributes_from_syntax - no longer needed with HasDocComments
+    #[test]
+    fn classifies_parser_errors() {
+        // Messages as emitted by ra_ap_parser/ra_ap_syntax; a change to any
+        // of them should be caught here rather than silently become `Other`
+        let cases = [
+            (
+                "fn f() { let x = 1 }",
+                "expected SEMICOLON",
+                ParseErrorCode::Expected,
+            ),
+            (
+                "fn f() {}}",
+                "unmatched `}`",
+                ParseErrorCode::UnmatchedDelimiter,
+            ),
+            (
+                "fn f() { let s = \"abc; }",
+                "Missing trailing `\"` symbol to terminate the string literal",
+                ParseErrorCode::UnterminatedLiteral,
+            ),
+            (
+                "/* never closed",
+                "Missing trailing `*/` symbols to terminate the block comment",
+                ParseErrorCode::UnterminatedLiteral,
+            ),
+            (
+                "fn f() { let r = r#\"abc; }",
+                "Invalid raw string literal",
+                ParseErrorCode::InvalidLiteral,
+            ),
+            (
+                "fn f() { let x = 0x; }",
+                "Missing digits after the integer base prefix",
+                ParseErrorCode::InvalidLiteral,
+            ),
+            (
+                "fn f() { let x = 1e; }",
+                "Missing digits after the exponent symbol",
+                ParseErrorCode::InvalidLiteral,
+            ),
+            (
+                "fn f() { let x = \"\\q\"; }",
+                "Invalid escape",
+                ParseErrorCode::InvalidLiteral,
+            ),
+            (
+                "fn f() { let x = ''; }",
+                "Literal must not be empty",
+                ParseErrorCode::InvalidLiteral,
+            ),
+            (
+                "fn f() { let x = foo\"bar\"; }",
+                "unknown literal prefix",
+                ParseErrorCode::InvalidLiteral,
+            ),
+            (
+                "fn f() { let x = #\"abc\"#; }",
+                "Invalid string literal (reserved syntax)",
+                ParseErrorCode::InvalidLiteral,
+            ),
+            (
+                "impl Trait for S { pub fn f() {} }",
+                "Unnecessary visibility qualifier",
+                ParseErrorCode::InvalidVisibility,
+            ),
+            (
+                "fn f<'1a>() {}",
+                "Lifetime name cannot start with a number",
+                ParseErrorCode::Other,
+            ),
+            (
+                "fn f<'k#a>() {}",
+                "Unknown lifetime prefix",
+                ParseErrorCode::Other,
+            ),
+        ];
+
+        for (code, message, expected) in cases {
+            let response = parse(code);
+            let error = response
+                .errors
+                .iter()
+                .find(|error| error.message == message)
+                .unwrap_or_else(|| panic!("no {message:?} error for {code:?}"));
+            assert_eq!(error.code, expected, "{message}");
+        }
+    }
+
+    fn item<'a>(items: &'a [ItemInfo], name: &str) -> &'a ItemInfo {
+        items
+            .iter()
+            .find(|item| item.name == name)
+            .unwrap_or_else(|| panic!("no item named {name}"))
+    }
+
+    fn names(items: &[ItemInfo]) -> Vec<&str> {
+        items.iter().map(|item| item.name.as_str()).collect()
+    }
+
+    fn adt<'a>(items: &'a [ItemInfo], name: &str) -> &'a AdtDetails {
+        match &item(items, name).details {
+            ItemDetails::Adt(adt) => adt,
+            _ => panic!("{name} isn't an ADT"),
+        }
+    }
+
+    fn module<'a>(items: &'a [ItemInfo], name: &str) -> &'a ModuleDetails {
+        match &item(items, name).details {
+            ItemDetails::Module(module) => module,
+            _ => panic!("{name} isn't a module"),
+        }
+    }
+
+    #[test]
+    fn matches_impls_to_their_self_type() {
+        let code = "pub struct Foo;\n\
+                    pub struct FooBar;\n\
+                    pub struct Bar;\n\
+                    impl Foo { pub fn on_foo(&self) {} }\n\
+                    impl FooBar { pub fn on_foo_bar(&self) {} }\n\
+                    impl From<Foo> for Bar { fn from(_: Foo) -> Self { Bar } }\n\
+                    impl<'a> IntoIterator for &'a Foo { fn into_iter(self) {} }\n\
+                    mod inner {\n\
+                        impl super::Foo { pub fn in_module(&self) {} }\n\
+                    }";
+        let items = parse(code).file_info.unwrap().items;
+
+        let foo = adt(&items, "Foo");
+        assert_eq!(names(&foo.methods), ["on_foo", "into_iter", "in_module"]);
+        let trait_paths: Vec<_> = foo.trait_impls.iter().map(|t| &t.trait_path).collect();
+        assert_eq!(trait_paths, ["IntoIterator"]);
+
+        let foo_bar = adt(&items, "FooBar");
+        assert_eq!(names(&foo_bar.methods), ["on_foo_bar"]);
+        assert!(foo_bar.trait_impls.is_empty());
+
+        let bar = adt(&items, "Bar");
+        assert_eq!(names(&bar.methods), ["from"]);
+        let trait_paths: Vec<_> = bar.trait_impls.iter().map(|t| &t.trait_path).collect();
+        assert_eq!(trait_paths, ["From<Foo>"]);
+    }
+
+    #[test]
+    fn leaves_restricted_and_hidden_items_out_of_the_public_api() {
+        let code = "pub fn public() {}\n\
+                    pub(crate) fn in_crate() {}\n\
+                    pub(super) fn in_super() {}\n\
+                    fn private() {}\n\
+                    #[doc(hidden)] pub fn hidden() {}\n\
+                    #[doc(hidden)] pub mod hidden_mod { pub fn f() {} }\n\
+                    mod private_mod { pub fn f() {} pub struct S; impl S { pub fn m(&self) {} } }\n\
+                    pub mod public_mod { pub fn f() {} fn g() {} }";
+
+        let public = parse_with(code, false, None).file_info.unwrap().items;
+        assert_eq!(names(&public), ["public", "public_mod"]);
+        assert_eq!(names(&module(&public, "public_mod").items), ["f"]);
+
+        let all = parse(code).file_info.unwrap().items;
+        let public_api: Vec<_> = all
+            .iter()
+            .filter(|item| item.is_public_api)
+            .cloned()
+            .collect();
+        assert_eq!(names(&public_api), ["public", "public_mod"]);
+
+        // `pub` doesn't reach past a private or hidden parent
+        for name in ["hidden_mod", "private_mod"] {
+            let items = &module(&all, name).items;
+            assert!(items.iter().all(|item| !item.is_public_api), "{name}");
+        }
+        let private_mod = module(&all, "private_mod");
+        assert!(!adt(&private_mod.items, "S").methods[0].is_public_api);
+    }
+
+    #[test]
+    fn lifts_exported_macros_out_of_private_modules() {
+        let code = "mod private {\n\
+                        #[macro_export]\n\
+                        macro_rules! exported { () => {} }\n\
+                        macro_rules! local { () => {} }\n\
+                        mod nested {\n\
+                            #[macro_export]\n\
+                            macro_rules! nested_exported { () => {} }\n\
+                        }\n\
+                    }";
+
+        let public = parse_with(code, false, None).file_info.unwrap().items;
+        assert_eq!(names(&public), ["exported", "nested_exported"]);
+        assert!(public.iter().all(|item| item.is_public_api));
+
+        // Kept in place, but still public, when private items are included
+        let all = parse(code).file_info.unwrap().items;
+        let private = &module(&all, "private").items;
+        assert!(item(private, "exported").is_public_api);
+        assert!(!item(private, "local").is_public_api);
+    }
+
+    #[test]
+    fn reads_proc_macro_names_and_helper_attributes() {
+        let code = "#[proc_macro_derive(Serialize, attributes(serde, rename))]\n\
+                    pub fn derive_serialize(input: TokenStream) -> TokenStream { input }\n\
+                    #[proc_macro_derive(Debug)]\n\
+                    pub fn derive_debug(input: TokenStream) -> TokenStream { input }\n\
+                    #[proc_macro_attribute]\n\
+                    pub fn route(attr: TokenStream, item: TokenStream) -> TokenStream { item }";
+        let items = parse(code).file_info.unwrap().items;
+        let macro_details = |name| match &item(&items, name).details {
+            ItemDetails::Macro(details) => details,
+            _ => panic!("{name} isn't a macro"),
+        };
+
+        // Derives are named after what they derive, not their function
+        let serialize = macro_details("Serialize");
+        assert!(matches!(serialize.kind, MacroKind::ProcMacroDerive));
+        assert_eq!(serialize.helper_attributes, ["serde", "rename"]);
+        assert_eq!(serialize.function_name.as_deref(), Some("derive_serialize"));
+
+        let debug = macro_details("Debug");
+        assert!(matches!(debug.kind, MacroKind::ProcMacroDerive));
+        assert!(debug.helper_attributes.is_empty());
+        assert_eq!(debug.function_name.as_deref(), Some("derive_debug"));
+
+        let route = macro_details("route");
+        assert!(matches!(route.kind, MacroKind::ProcMacroAttribute));
+        assert!(route.helper_attributes.is_empty());
+        assert_eq!(route.function_name.as_deref(), Some("route"));
+    }
+
+    #[test]
+    fn flattens_use_trees() {
+        let code = "pub use a::{b as c, d::*, self, e::{self as ee}};\n\
+                    use ::std::fmt;\n\
+                    pub(crate) use x::y;";
+        let items = parse(code).file_info.unwrap().items;
+        let imports: Vec<_> = items
+            .iter()
+            .flat_map(|item| match &item.details {
+                ItemDetails::Use(details) => details.imports.iter(),
+                _ => panic!("{} isn't a use", item.name),
+            })
+            .map(|import| {
+                (
+                    import.path.as_str(),
+                    import.name.as_str(),
+                    import.alias.as_deref(),
+                    import.is_glob,
+                    import.is_reexport,
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            imports,
+            [
+                ("a::b", "b", Some("c"), false, true),
+                ("a::d", "*", None, true, true),
+                ("a", "a", None, false, true),
+                ("a::e", "e", Some("ee"), false, true),
+                // The leading `::` stays in the path
+                ("::std::fmt", "fmt", None, false, false),
+                // Only a bare `pub` re-exports out of the crate
+                ("x::y", "y", None, false, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn evaluates_cfg_only_when_asked() {
+        let code = "#[cfg(feature = \"std\")]\n\
+                    pub mod io { pub fn read() {} }\n\
+                    #[cfg(not(feature = \"std\"))]\n\
+                    pub fn fallback() {}";
+        // (io, io::read, fallback)
+        let active = |cfg: Option<&[&str]>| {
+            let items = parse_with(code, true, cfg).file_info.unwrap().items;
+            let io = item(&items, "io");
+            let ItemDetails::Module(module) = &io.details else {
+                panic!("io isn't a module");
+            };
+            let fallback = item(&items, "fallback");
+            (
+                io.is_cfg_active,
+                module.items[0].is_cfg_active,
+                fallback.is_cfg_active,
+            )
+        };
+
+        // Without options nothing is evaluated, so nothing is hidden
+        assert_eq!(active(None), (true, true, true));
+        assert_eq!(active(Some(&[])), (false, false, true));
+        assert_eq!(active(Some(&["feature=\"std\""])), (true, true, false));
+    }
+
+    fn module_paths(file_path: &str, code: &str) -> Vec<(String, Vec<String>)> {
+        let request = ParseRequest {
+            code: code.to_string(),
+            file_path: Some(file_path.to_string()),
+            include_private: true,
+            cfg: None,
+            edition: None,
+            manifest: None,
+        };
+        let file_info = parse_rust_code(&request).unwrap().file_info.unwrap();
+
+        let mut paths = Vec::new();
+        collect_module_paths(&file_info.items, &file_info.module_references, &mut paths);
+        paths
+    }
+
+    fn collect_module_paths(
+        items: &[ItemInfo],
+        module_references: &[ModuleReference],
+        paths: &mut Vec<(String, Vec<String>)>,
+    ) {
+        for reference in module_references {
+            paths.push((reference.name.clone(), reference.expected_paths.clone()));
+        }
+        for item in items {
+            if let ItemDetails::Module(module) = &item.details {
+                collect_module_paths(&module.items, &module.module_references, paths);
+            }
+        }
+    }
+
+    #[test]
+    fn normalizes_paths() {
+        assert_eq!(normalize_path("src/./a.rs"), "src/a.rs");
+        assert_eq!(normalize_path("src/a/../b.rs"), "src/b.rs");
+        assert_eq!(normalize_path("src//a.rs"), "src/a.rs");
+        assert_eq!(normalize_path("../a.rs"), "../a.rs");
+        assert_eq!(normalize_path("src/../../a.rs"), "../a.rs");
+        assert_eq!(normalize_path("../../a.rs"), "../../a.rs");
+        assert_eq!(normalize_path("/src/../a.rs"), "/a.rs");
+        assert_eq!(normalize_path("/../a.rs"), "/a.rs");
+    }
+
+    #[test]
+    fn joins_paths() {
+        assert_eq!(join_path("", "a.rs"), "a.rs");
+        assert_eq!(join_path("src", "a.rs"), "src/a.rs");
+        assert_eq!(join_path("src", "/abs/a.rs"), "/abs/a.rs");
+    }
+
+    #[test]
+    fn places_child_modules_by_file_name() {
+        assert_eq!(file_module_dir("src/lib.rs"), "");
+        assert_eq!(file_module_dir("src/main.rs"), "");
+        assert_eq!(file_module_dir("src/foo/mod.rs"), "");
+        assert_eq!(file_module_dir("src/foo.rs"), "foo");
+        assert_eq!(file_module_dir("foo.rs"), "foo");
+        assert_eq!(file_module_dir(""), "");
+    }
+
+    #[test]
+    fn resolves_expected_paths_against_the_file() {
+        let code = "mod a;\n\
+                    #[path = \"other/b.rs\"] mod b;\n\
+                    mod inline {\n\
+                        mod c;\n\
+                        #[path = \"../d.rs\"] mod d;\n\
+                    }\n\
+                    #[path = \"renamed\"] mod e { mod f; }";
+        assert_eq!(
+            module_paths("src/foo.rs", code),
+            vec![
+                (
+                    "a".into(),
+                    vec!["src/foo/a.rs".into(), "src/foo/a/mod.rs".into()]
+                ),
+                ("b".into(), vec!["src/other/b.rs".into()]),
+                (
+                    "c".into(),
+                    vec![
+                        "src/foo/inline/c.rs".into(),
+                        "src/foo/inline/c/mod.rs".into()
+                    ]
+                ),
+                ("d".into(), vec!["src/foo/d.rs".into()]),
+                (
+                    "f".into(),
+                    vec![
+                        "src/foo/renamed/f.rs".into(),
+                        "src/foo/renamed/f/mod.rs".into()
+                    ]
+                ),
+            ]
+        );
+        assert_eq!(
+            module_paths("src/lib.rs", "mod a;"),
+            vec![("a".into(), vec!["src/a.rs".into(), "src/a/mod.rs".into()])]
+        );
+    }
+}
@@ -0,0 +1,161 @@
+use ra_ap_syntax::{ast, AstNode, NodeOrToken, SyntaxKind};
+
+use super::{AttributeInfo, Deprecation, InlineKind, KnownAttributes, LinkInfo};
+
+/// Outer attributes attached directly to `node`.
+pub fn extract_attributes<T: AstNode>(node: &T) -> Vec<AttributeInfo> {
+    node.syntax()
+        .children()
+        .filter_map(ast::Attr::cast)
+        .filter_map(|attr| extract_attribute(&attr))
+        .collect()
+}
+
+fn extract_attribute(attr: &ast::Attr) -> Option<AttributeInfo> {
+    let meta = attr.meta()?;
+    let path = meta.path()?.syntax().text().to_string();
+    let args = meta
+        .token_tree()
+        .map(|tt| split_token_tree(&tt))
+        .unwrap_or_default();
+    let value = meta.expr().map(|expr| expr.syntax().text().to_string());
+
+    Some(AttributeInfo { path, args, value })
+}
+
+/// Splits the contents of a delimited token tree on its top-level commas.
+///
+/// Commas inside nested delimiters belong to nested token trees, so only the
+/// direct children of `tt` need to be considered.
+fn split_token_tree(tt: &ast::TokenTree) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+
+    let children: Vec<_> = tt.syntax().children_with_tokens().collect();
+    let last = children.len().saturating_sub(1);
+    for (index, child) in children.iter().enumerate() {
+        match child {
+            NodeOrToken::Token(token)
+                if is_delimiter(token.kind()) && (index == 0 || index == last) => {}
+            NodeOrToken::Token(token) if token.kind() == SyntaxKind::COMMA => {
+                push_arg(&mut args, &current);
+                current.clear();
+            }
+            NodeOrToken::Token(token) => current.push_str(token.text()),
+            NodeOrToken::Node(node) => current.push_str(&node.text().to_string()),
+        }
+    }
+    push_arg(&mut args, &current);
+
+    args
+}
+
+fn push_arg(args: &mut Vec<String>, arg: &str) {
+    let arg = arg.trim();
+    if !arg.is_empty() {
+        args.push(arg.to_string());
+    }
+}
+
+fn is_delimiter(kind: SyntaxKind) -> bool {
+    matches!(
+        kind,
+        SyntaxKind::L_PAREN
+            | SyntaxKind::R_PAREN
+            | SyntaxKind::L_BRACK
+            | SyntaxKind::R_BRACK
+            | SyntaxKind::L_CURLY
+            | SyntaxKind::R_CURLY
+    )
+}
+
+impl KnownAttributes {
+    pub fn from_attributes(attributes: &[AttributeInfo]) -> Self {
+        let mut known = KnownAttributes::default();
+
+        for attr in attributes {
+            match attr.path.as_str() {
+                "derive" => known.derives.extend(attr.args.iter().cloned()),
+                "must_use" => known.is_must_use = true,
+                "non_exhaustive" => known.is_non_exhaustive = true,
+                "inline" => {
+                    known.inline = Some(match attr.args.first().map(String::as_str) {
+                        Some("always") => InlineKind::Always,
+                        Some("never") => InlineKind::Never,
+                        _ => InlineKind::Hint,
+                    })
+                }
+                "repr" => known.repr.extend(attr.args.iter().cloned()),
+                "deprecated" => {
+                    let mut deprecation = Deprecation {
+                        since: None,
+                        note: attr.value.as_deref().map(unquote),
+                    };
+                    for arg in &attr.args {
+                        match arg.split_once('=') {
+                            Some((key, value)) if key.trim() == "since" => {
+                                deprecation.since = Some(unquote(value))
+                            }
+                            Some((key, value)) if key.trim() == "note" => {
+                                deprecation.note = Some(unquote(value))
+                            }
+                            _ => {}
+                        }
+                    }
+                    known.deprecated = Some(deprecation);
+                }
+                "doc" if attr.args.iter().any(|arg| arg == "hidden") => known.is_doc_hidden = true,
+                _ => {}
+            }
+        }
+
+        known
+    }
+}
+
+/// `#[link(name = "...", kind = "...", modifiers = "...")]` attributes, as
+/// found on extern blocks.
+pub fn extract_links(attributes: &[AttributeInfo]) -> Vec<LinkInfo> {
+    attributes
+        .iter()
+        .filter(|attr| attr.path == "link")
+        .map(|attr| {
+            let mut link = LinkInfo::default();
+            for arg in &attr.args {
+                match arg
+                    .split_once('=')
+                    .map(|(key, value)| (key.trim(), unquote(value)))
+                {
+                    Some(("name", value)) => link.name = Some(value),
+                    Some(("kind", value)) => link.kind = Some(value),
+                    Some(("modifiers", value)) => link.modifiers = Some(value),
+                    _ => {}
+                }
+            }
+            link
+        })
+        .collect()
+}
+
+/// The value of a `#[path = "..."]` attribute.
+pub fn extract_path_attribute(attributes: &[AttributeInfo]) -> Option<String> {
+    attributes
+        .iter()
+        .find(|attr| attr.path == "path")
+        .and_then(|attr| attr.value.as_deref())
+        .map(unquote)
+}
+
+/// Strips the quotes from a string literal as written in an attribute.
+///
+/// Escapes are left as-is, which is fine for the short values we read here.
+pub fn unquote(literal: &str) -> String {
+    let literal = literal.trim();
+    let literal = literal.strip_prefix('r').unwrap_or(literal);
+    literal
+        .trim_matches('#')
+        .strip_prefix('"')
+        .and_then(|s| s.strip_suffix('"'))
+        .unwrap_or(literal)
+        .to_string()
+}
@@ -0,0 +1,317 @@
+use std::collections::HashSet;
+use std::iter::Peekable;
+use std::str::Chars;
+
+/// A parsed `#[cfg(...)]` predicate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CfgExpr {
+    Atom(String),             // unix, test, debug_assertions
+    KeyValue(String, String), // feature = "serde", target_os = "linux"
+    All(Vec<CfgExpr>),
+    Any(Vec<CfgExpr>),
+    Not(Box<CfgExpr>),
+}
+
+impl CfgExpr {
+    /// Parses the contents of `cfg(...)`, e.g. `all(unix, feature = "std")`.
+    pub fn parse(text: &str) -> Option<CfgExpr> {
+        let tokens = tokenize(text)?;
+        let mut pos = 0;
+        let expr = parse_expr(&tokens, &mut pos)?;
+        // Allow a single trailing comma, as rustc does
+        if tokens.get(pos) == Some(&Token::Comma) {
+            pos += 1;
+        }
+        (pos == tokens.len()).then_some(expr)
+    }
+
+    pub fn eval(&self, options: &CfgOptions) -> bool {
+        match self {
+            CfgExpr::Atom(name) => options.atoms.contains(name),
+            CfgExpr::KeyValue(key, value) => {
+                options.key_values.contains(&(key.clone(), value.clone()))
+            }
+            CfgExpr::All(exprs) => exprs.iter().all(|expr| expr.eval(options)),
+            CfgExpr::Any(exprs) => exprs.iter().any(|expr| expr.eval(options)),
+            CfgExpr::Not(expr) => !expr.eval(options),
+        }
+    }
+}
+
+/// The set of active cfg options a file is evaluated against.
+#[derive(Debug, Clone, Default)]
+pub struct CfgOptions {
+    atoms: HashSet<String>,
+    key_values: HashSet<(String, String)>,
+}
+
+impl CfgOptions {
+    /// Builds the set from options in rustc's `--cfg` syntax: `unix` or
+    /// `feature="serde"`. Options that don't parse are ignored.
+    pub fn from_specs<S: AsRef<str>>(specs: &[S]) -> Self {
+        let mut options = CfgOptions::default();
+        for spec in specs {
+            match CfgExpr::parse(spec.as_ref()) {
+                Some(CfgExpr::Atom(name)) => {
+                    options.atoms.insert(name);
+                }
+                Some(CfgExpr::KeyValue(key, value)) => {
+                    options.key_values.insert((key, value));
+                }
+                _ => tracing::warn!("Ignoring invalid cfg option: {}", spec.as_ref()),
+            }
+        }
+        options
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Ident(String),
+    Str(String),
+    Eq,
+    Comma,
+    OpenParen,
+    CloseParen,
+}
+
+fn tokenize(text: &str) -> Option<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = text.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '=' => {
+                chars.next();
+                tokens.push(Token::Eq);
+            }
+            ',' => {
+                chars.next();
+                tokens.push(Token::Comma);
+            }
+            '(' => {
+                chars.next();
+                tokens.push(Token::OpenParen);
+            }
+            ')' => {
+                chars.next();
+                tokens.push(Token::CloseParen);
+            }
+            '"' => {
+                chars.next();
+                let mut value = String::new();
+                loop {
+                    match chars.next()? {
+                        '"' => break,
+                        '\\' => value.push(chars.next()?),
+                        c => value.push(c),
+                    }
+                }
+                tokens.push(Token::Str(value));
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                let mut ident = String::new();
+                while let Some(&c) = chars.peek() {
+                    if !(c.is_alphanumeric() || c == '_' || c == ':') {
+                        break;
+                    }
+                    ident.push(c);
+                    chars.next();
+                }
+                if ident == "r" && matches!(chars.peek(), Some('"' | '#')) {
+                    tokens.push(Token::Str(raw_string(&mut chars)?));
+                } else {
+                    tokens.push(Token::Ident(ident));
+                }
+            }
+            _ => return None,
+        }
+    }
+
+    Some(tokens)
+}
+
+/// The rest of a raw string literal, after its `r`.
+fn raw_string(chars: &mut Peekable<Chars>) -> Option<String> {
+    let mut hashes = 0;
+    while chars.next_if_eq(&'#').is_some() {
+        hashes += 1;
+    }
+    if chars.next()? != '"' {
+        return None;
+    }
+
+    let mut value = String::new();
+    loop {
+        match chars.next()? {
+            '"' => {
+                let mut closing = 0;
+                while closing < hashes && chars.next_if_eq(&'#').is_some() {
+                    closing += 1;
+                }
+                if closing == hashes {
+                    return Some(value);
+                }
+                value.push('"');
+                value.extend(std::iter::repeat_n('#', closing));
+            }
+            c => value.push(c),
+        }
+    }
+}
+
+fn parse_expr(tokens: &[Token], pos: &mut usize) -> Option<CfgExpr> {
+    let Token::Ident(name) = tokens.get(*pos)? else {
+        return None;
+    };
+    *pos += 1;
+
+    match tokens.get(*pos) {
+        Some(Token::Eq) => {
+            *pos += 1;
+            let Token::Str(value) = tokens.get(*pos)? else {
+                return None;
+            };
+            *pos += 1;
+            Some(CfgExpr::KeyValue(name.clone(), value.clone()))
+        }
+        Some(Token::OpenParen) => {
+            *pos += 1;
+            let args = parse_args(tokens, pos)?;
+            match name.as_str() {
+                "all" => Some(CfgExpr::All(args)),
+                "any" => Some(CfgExpr::Any(args)),
+                "not" if args.len() == 1 => Some(CfgExpr::Not(Box::new(args.into_iter().next()?))),
+                _ => None,
+            }
+        }
+        _ => Some(CfgExpr::Atom(name.clone())),
+    }
+}
+
+fn parse_args(tokens: &[Token], pos: &mut usize) -> Option<Vec<CfgExpr>> {
+    let mut args = Vec::new();
+
+    loop {
+        if tokens.get(*pos)? == &Token::CloseParen {
+            *pos += 1;
+            return Some(args);
+        }
+        args.push(parse_expr(tokens, pos)?);
+        match tokens.get(*pos)? {
+            Token::Comma => *pos += 1,
+            Token::CloseParen => {}
+            _ => return None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(specs: &[&str]) -> CfgOptions {
+        CfgOptions::from_specs(specs)
+    }
+
+    fn eval(text: &str, specs: &[&str]) -> bool {
+        CfgExpr::parse(text).unwrap().eval(&options(specs))
+    }
+
+    #[test]
+    fn parses_atoms_and_key_values() {
+        assert_eq!(CfgExpr::parse("unix"), Some(CfgExpr::Atom("unix".into())));
+        assert_eq!(
+            CfgExpr::parse(r#"feature = "serde""#),
+            Some(CfgExpr::KeyValue("feature".into(), "serde".into()))
+        );
+        assert_eq!(
+            CfgExpr::parse(r#"feature = r"serde""#),
+            Some(CfgExpr::KeyValue("feature".into(), "serde".into()))
+        );
+        assert_eq!(
+            CfgExpr::parse(r##"feature = r#"a"b"#"##),
+            Some(CfgExpr::KeyValue("feature".into(), "a\"b".into()))
+        );
+        assert_eq!(
+            CfgExpr::parse(r#"feature="a\"b""#),
+            Some(CfgExpr::KeyValue("feature".into(), "a\"b".into()))
+        );
+    }
+
+    #[test]
+    fn parses_nested_predicates() {
+        assert_eq!(
+            CfgExpr::parse(r#"all(unix, not(feature = "std"))"#),
+            Some(CfgExpr::All(vec![
+                CfgExpr::Atom("unix".into()),
+                CfgExpr::Not(Box::new(CfgExpr::KeyValue("feature".into(), "std".into()))),
+            ]))
+        );
+        assert_eq!(CfgExpr::parse("any()"), Some(CfgExpr::Any(vec![])));
+    }
+
+    #[test]
+    fn allows_trailing_commas() {
+        assert_eq!(
+            CfgExpr::parse("all(unix, windows,)"),
+            Some(CfgExpr::All(vec![
+                CfgExpr::Atom("unix".into()),
+                CfgExpr::Atom("windows".into()),
+            ]))
+        );
+        assert_eq!(CfgExpr::parse("unix,"), Some(CfgExpr::Atom("unix".into())));
+        assert_eq!(CfgExpr::parse("unix,,"), None);
+        assert_eq!(CfgExpr::parse("all(unix,,)"), None);
+    }
+
+    #[test]
+    fn rejects_malformed_predicates() {
+        assert_eq!(CfgExpr::parse(""), None);
+        assert_eq!(CfgExpr::parse("not()"), None);
+        assert_eq!(CfgExpr::parse("not(unix, windows)"), None);
+        assert_eq!(CfgExpr::parse("maybe(unix)"), None);
+        assert_eq!(CfgExpr::parse("all(unix"), None);
+        assert_eq!(CfgExpr::parse(r#"feature = "serde"#), None);
+        assert_eq!(CfgExpr::parse("feature = serde"), None);
+        assert_eq!(CfgExpr::parse("unix windows"), None);
+        assert_eq!(CfgExpr::parse(r#"feature = r"serde"#), None);
+        assert_eq!(CfgExpr::parse("r#unix"), None);
+    }
+
+    #[test]
+    fn evaluates_against_options() {
+        let specs = ["unix", r#"feature="std""#];
+        assert!(eval("unix", &specs));
+        assert!(!eval("windows", &specs));
+        assert!(eval(r#"feature = "std""#, &specs));
+        assert!(!eval(r#"feature = "alloc""#, &specs));
+        assert!(eval(r#"all(unix, feature = "std")"#, &specs));
+        assert!(!eval("all(unix, windows)", &specs));
+        assert!(eval("any(windows, unix)", &specs));
+        assert!(eval("not(windows)", &specs));
+        // Empty all() holds and empty any() doesn't, as in rustc
+        assert!(eval("all()", &[]));
+        assert!(!eval("any()", &[]));
+    }
+
+    #[test]
+    fn key_values_only_match_exactly() {
+        let specs = [r#"feature="std""#];
+        assert!(!eval("feature", &specs));
+        assert!(!eval("std", &specs));
+        assert!(!eval(r#"feature = "std""#, &["feature"]));
+    }
+
+    #[test]
+    fn ignores_invalid_specs() {
+        let options = options(&["unix", "not(windows)", "feature=", r#"target_os="linux""#]);
+        assert!(CfgExpr::Atom("unix".into()).eval(&options));
+        assert!(CfgExpr::KeyValue("target_os".into(), "linux".into()).eval(&options));
+        assert!(!CfgExpr::Atom("windows".into()).eval(&options));
+        assert!(!CfgExpr::Atom("feature".into()).eval(&options));
+    }
+}
@@ -0,0 +1,801 @@
+use ra_ap_syntax::{ast, AstNode, NodeOrToken, SyntaxKind, SyntaxNode, TextRange};
+
+use super::{line_index::LineIndex, text_range_to_byte_offsets, DocSections, Doctest};
+
+/// One piece of documentation as written in the source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct DocFragment {
+    kind: FragmentKind,
+    text: String,
+    range: TextRange, // The comment or attribute the text comes from
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FragmentKind {
+    Sugared, // `///`, `//!`, `/** */` or `/*! */`, with the comment markers removed
+    Raw,     // The unescaped string of a `#[doc = "..."]` attribute
+}
+
+/// A line of rendered docs, along with the fragment it was written in.
+struct DocLine {
+    text: String,
+    range: TextRange,
+}
+
+/// Outer documentation of `node`: `///`, `/** */` and `#[doc = "..."]`.
+///
+/// For an inline module this includes the `//!` docs inside it, which
+/// rustdoc shows after the outer docs.
+pub fn extract_doc_comment<N: AstNode>(node: &N) -> Option<String> {
+    join_lines(&render_docs(&item_fragments(node.syntax())))
+}
+
+/// Inner documentation held by `node`: `//!`, `/*! */` and `#![doc = "..."]`.
+///
+/// `node` is the container the docs are written in, i.e. the source file or
+/// a module's item list.
+pub fn extract_inner_doc_comment<N: AstNode>(node: &N) -> Option<String> {
+    join_lines(&render_docs(&collect_fragments(node.syntax(), true)))
+}
+
+/// Every fenced code block in the docs of `node`, in the order written.
+///
+/// Locations cover the doc lines from the opening to the closing fence. Block
+/// comments and `#[doc]` attributes spanning several lines can only be
+/// located as a whole.
+pub fn extract_doctests<N: AstNode>(node: &N, line_index: &LineIndex) -> Vec<Doctest> {
+    let lines = render_docs(&item_fragments(node.syntax()));
+    let mut doctests = Vec::new();
+
+    let mut index = 0;
+    while index < lines.len() {
+        let Some((indent, fence, info)) = opening_fence(&lines[index].text) else {
+            index += 1;
+            continue;
+        };
+
+        // An unclosed block runs to the end of the docs, as in CommonMark
+        let end = lines[index + 1..]
+            .iter()
+            .position(|line| is_closing_fence(&line.text, fence))
+            .map_or(lines.len(), |offset| index + 1 + offset);
+        let code: Vec<&str> = lines[index + 1..end]
+            .iter()
+            .map(|line| strip_indent(&line.text, indent))
+            .collect();
+        let range = lines[index]
+            .range
+            .cover(lines[end.min(lines.len() - 1)].range);
+
+        doctests.push(doctest(info, &code, range, line_index));
+        index = end + 1;
+    }
+
+    doctests
+}
+
+fn item_fragments(node: &SyntaxNode) -> Vec<DocFragment> {
+    let mut fragments = collect_fragments(node, false);
+    if let Some(item_list) = ast::Module::cast(node.clone()).and_then(|m| m.item_list()) {
+        fragments.extend(collect_fragments(item_list.syntax(), true));
+    }
+    fragments
+}
+
+fn collect_fragments(node: &SyntaxNode, inner: bool) -> Vec<DocFragment> {
+    node.children_with_tokens()
+        .filter_map(|child| match child {
+            NodeOrToken::Token(token) if token.kind() == SyntaxKind::COMMENT => {
+                let (kind, text) = comment_fragment(token.text(), inner)?;
+                Some(DocFragment {
+                    kind,
+                    text,
+                    range: token.text_range(),
+                })
+            }
+            NodeOrToken::Node(node) => {
+                let attr = ast::Attr::cast(node)?;
+                if attr.excl_token().is_some() != inner {
+                    return None;
+                }
+                let meta = attr.meta()?;
+                if meta.path()?.syntax().text() != "doc" {
+                    return None;
+                }
+                let literal = meta.expr()?.syntax().text().to_string();
+                Some(DocFragment {
+                    kind: FragmentKind::Raw,
+                    text: unescape_string_literal(&literal)?,
+                    range: attr.syntax().text_range(),
+                })
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+fn comment_fragment(text: &str, inner: bool) -> Option<(FragmentKind, String)> {
+    let (line_prefix, block_prefix) = if inner {
+        ("//!", "/*!")
+    } else {
+        ("///", "/**")
+    };
+
+    if let Some(content) = text.strip_prefix(line_prefix) {
+        // `////` is an ordinary comment
+        if !inner && content.starts_with('/') {
+            return None;
+        }
+        return Some((FragmentKind::Sugared, content.to_string()));
+    }
+
+    let content = text.strip_prefix(block_prefix)?.strip_suffix("*/")?;
+    // `/*** ... */` is an ordinary comment
+    if !inner && content.starts_with('*') {
+        return None;
+    }
+    Some((FragmentKind::Sugared, beautify_block_doc(content)))
+}
+
+/// Strips the decoration block docs are usually written with: blank first
+/// and last lines, and a column of leading `*`.
+///
+/// As in rustdoc, a first line without a `*` doesn't stop the column from
+/// being stripped, so `/** Summary` can start a starred comment.
+fn beautify_block_doc(content: &str) -> String {
+    let mut lines: Vec<&str> = content.lines().collect();
+    if lines.len() > 1 {
+        while lines.first().is_some_and(|line| line.trim().is_empty()) {
+            lines.remove(0);
+        }
+        while lines
+            .last()
+            .is_some_and(|line| line.trim().trim_start_matches('*').is_empty())
+        {
+            lines.pop();
+        }
+    }
+
+    let is_starred = |line: &&str| line.trim_start().starts_with('*');
+    let skip = usize::from(lines.first().is_some_and(|line| !is_starred(line)));
+    let has_star_column = lines.len() > 1
+        && lines[skip..]
+            .iter()
+            .all(|line| line.trim().is_empty() || is_starred(line));
+    if has_star_column {
+        for line in &mut lines[skip..] {
+            *line = line.trim_start().strip_prefix('*').unwrap_or("");
+        }
+    }
+
+    lines.join("\n")
+}
+
+/// Splits fragments into the lines of one doc string, removing their common
+/// indentation the way rustdoc does.
+///
+/// `///` lines conventionally start with a space that `#[doc = "..."]`
+/// values don't, so when both forms are mixed raw fragments are treated as
+/// one column further indented than they're written.
+fn render_docs(fragments: &[DocFragment]) -> Vec<DocLine> {
+    let is_mixed = fragments.iter().any(|f| f.kind == FragmentKind::Sugared)
+        && fragments.iter().any(|f| f.kind == FragmentKind::Raw);
+    let raw_offset = usize::from(is_mixed);
+
+    let min_indent = fragments
+        .iter()
+        .flat_map(|fragment| {
+            let offset = match fragment.kind {
+                FragmentKind::Sugared => 0,
+                FragmentKind::Raw => raw_offset,
+            };
+            fragment
+                .text
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(move |line| indent_width(line) + offset)
+        })
+        .min()
+        .unwrap_or(0);
+
+    let mut lines = Vec::new();
+    for fragment in fragments {
+        let indent = match fragment.kind {
+            FragmentKind::Sugared => min_indent,
+            FragmentKind::Raw => min_indent.saturating_sub(raw_offset),
+        };
+        // An empty `///` line is still a (blank) line of the docs
+        if fragment.text.is_empty() {
+            lines.push(DocLine {
+                text: String::new(),
+                range: fragment.range,
+            });
+        }
+        for line in fragment.text.lines() {
+            lines.push(DocLine {
+                text: strip_indent(line, indent).trim_end().to_string(),
+                range: fragment.range,
+            });
+        }
+    }
+
+    while lines.last().is_some_and(|line| line.text.is_empty()) {
+        lines.pop();
+    }
+    while lines.first().is_some_and(|line| line.text.is_empty()) {
+        lines.remove(0);
+    }
+
+    lines
+}
+
+fn join_lines(lines: &[DocLine]) -> Option<String> {
+    if lines.is_empty() {
+        return None;
+    }
+    let text: Vec<&str> = lines.iter().map(|line| line.text.as_str()).collect();
+    Some(text.join("\n"))
+}
+
+fn indent_width(line: &str) -> usize {
+    line.chars().take_while(|c| c.is_whitespace()).count()
+}
+
+fn strip_indent(line: &str, indent: usize) -> &str {
+    let offset = line
+        .char_indices()
+        .take(indent)
+        .take_while(|(_, c)| c.is_whitespace())
+        .map(|(offset, c)| offset + c.len_utf8())
+        .last()
+        .unwrap_or(0);
+    &line[offset..]
+}
+
+/// The value of a string literal, with escapes processed.
+///
+/// Returns `None` for anything that isn't a plain or raw string literal,
+/// such as `#[doc = include_str!("../README.md")]`.
+fn unescape_string_literal(literal: &str) -> Option<String> {
+    if let Some(raw) = literal.strip_prefix('r') {
+        let hashes = raw.len() - raw.trim_start_matches('#').len();
+        let raw = &raw[hashes..raw.len().checked_sub(hashes)?];
+        return Some(raw.strip_prefix('"')?.strip_suffix('"')?.to_string());
+    }
+
+    let content = literal.strip_prefix('"')?.strip_suffix('"')?;
+    let mut value = String::with_capacity(content.len());
+    let mut chars = content.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            value.push(c);
+            continue;
+        }
+        match chars.next()? {
+            'n' => value.push('\n'),
+            'r' => value.push('\r'),
+            't' => value.push('\t'),
+            '0' => value.push('\0'),
+            'x' => {
+                let hex: String = chars.by_ref().take(2).collect();
+                value.push(char::from(u8::from_str_radix(&hex, 16).ok()?));
+            }
+            'u' => {
+                let hex: String = chars
+                    .by_ref()
+                    .skip_while(|c| *c == '{')
+                    .take_while(|c| *c != '}')
+                    .collect();
+                value.push(char::from_u32(u32::from_str_radix(&hex, 16).ok()?)?);
+            }
+            // A line continuation skips the newline and leading whitespace
+            '\n' => while chars.next_if(|c| c.is_whitespace()).is_some() {},
+            other => value.push(other),
+        }
+    }
+    Some(value)
+}
+
+/// Splits a doc string into its summary, body and the conventional rustdoc
+/// sections.
+///
+/// The summary is the first paragraph, as rustdoc shows it in item lists.
+/// A section runs from its heading to the next heading of the same or a
+/// higher level; headings inside code blocks don't count.
+pub fn parse_doc_sections(doc: &str) -> DocSections {
+    let mut sections = DocSections::default();
+    let mut body = Vec::new();
+    let mut current: Option<(usize, KnownSection, Vec<&str>)> = None;
+    let mut fence: Option<&str> = None;
+
+    let lines: Vec<&str> = doc.lines().collect();
+    let summary_end = lines
+        .iter()
+        .position(|line| line.trim().is_empty() || heading(line).is_some())
+        .unwrap_or(lines.len());
+    let summary = lines[..summary_end]
+        .iter()
+        .map(|line| line.trim())
+        .collect::<Vec<_>>()
+        .join(" ");
+    if !summary.is_empty() {
+        sections.summary = Some(summary);
+    }
+
+    for line in &lines[summary_end..] {
+        let trimmed = line.trim_start();
+        if let Some(marker) = fence {
+            if trimmed.starts_with(marker) {
+                fence = None;
+            }
+        } else if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            fence = Some(&trimmed[..3]);
+        } else if let Some((level, title)) = heading(line) {
+            let ends_current = current
+                .as_ref()
+                .is_some_and(|(current_level, _, _)| level <= *current_level);
+            if current.is_none() || ends_current {
+                if let Some(section) = current.take() {
+                    sections.set(section.1, &section.2);
+                }
+                if let Some(known) = KnownSection::from_title(title) {
+                    current = Some((level, known, Vec::new()));
+                    continue;
+                }
+            }
+        }
+
+        match &mut current {
+            Some((_, _, section_lines)) => section_lines.push(line),
+            None => body.push(*line),
+        }
+    }
+    if let Some(section) = current {
+        sections.set(section.1, &section.2);
+    }
+    sections.body = join_paragraphs(&body);
+
+    sections
+}
+
+#[derive(Debug, Clone, Copy)]
+enum KnownSection {
+    Examples,
+    Errors,
+    Panics,
+    Safety,
+}
+
+impl KnownSection {
+    fn from_title(title: &str) -> Option<Self> {
+        match title.trim().to_lowercase().as_str() {
+            "examples" | "example" => Some(KnownSection::Examples),
+            "errors" => Some(KnownSection::Errors),
+            "panics" => Some(KnownSection::Panics),
+            "safety" => Some(KnownSection::Safety),
+            _ => None,
+        }
+    }
+}
+
+impl DocSections {
+    fn set(&mut self, section: KnownSection, lines: &[&str]) {
+        let content = join_paragraphs(lines);
+        match section {
+            KnownSection::Examples => self.examples = content,
+            KnownSection::Errors => self.errors = content,
+            KnownSection::Panics => self.panics = content,
+            KnownSection::Safety => self.safety = content,
+        }
+    }
+}
+
+/// An ATX heading's level and title: `## Errors` is `(2, "Errors")`.
+fn heading(line: &str) -> Option<(usize, &str)> {
+    let line = line.trim_start();
+    let level = line.len() - line.trim_start_matches('#').len();
+    let title = &line[level..];
+    if (1..=6).contains(&level) && (title.is_empty() || title.starts_with(' ')) {
+        Some((level, title.trim().trim_end_matches('#').trim_end()))
+    } else {
+        None
+    }
+}
+
+fn join_paragraphs(lines: &[&str]) -> Option<String> {
+    let text = lines.join("\n");
+    let text = text.trim_matches('\n');
+    if text.trim().is_empty() {
+        None
+    } else {
+        Some(text.to_string())
+    }
+}
+
+/// A fence opening a code block: its indentation, the fence itself
+/// (`` ``` `` or `~~~`, possibly longer) and the info string after it.
+fn opening_fence(line: &str) -> Option<(usize, &str, &str)> {
+    let trimmed = line.trim_start();
+    let marker = trimmed.chars().next().filter(|c| *c == '`' || *c == '~')?;
+    let length = trimmed.len() - trimmed.trim_start_matches(marker).len();
+    if length < 3 {
+        return None;
+    }
+    let (fence, info) = trimmed.split_at(length);
+    // Backticks can't appear in the info string of a backtick fence
+    if marker == '`' && info.contains('`') {
+        return None;
+    }
+    Some((indent_width(line), fence, info.trim()))
+}
+
+fn is_closing_fence(line: &str, fence: &str) -> bool {
+    let trimmed = line.trim();
+    let marker = fence.chars().next();
+    trimmed.len() >= fence.len() && trimmed.chars().all(|c| Some(c) == marker)
+}
+
+fn doctest(info: &str, lines: &[&str], range: TextRange, line_index: &LineIndex) -> Doctest {
+    let (language, attributes) = parse_code_block_info(info);
+
+    let mut code = Vec::new();
+    let mut visible_code = Vec::new();
+    for line in lines {
+        // Only Rust blocks have hidden lines; other languages keep their `#`s
+        let (text, is_hidden) = if language == "rust" {
+            hidden_line(line)
+        } else {
+            (line.to_string(), false)
+        };
+        if !is_hidden {
+            visible_code.push(text.clone());
+        }
+        code.push(text);
+    }
+
+    Doctest {
+        language,
+        attributes,
+        code: code.join("\n"),
+        visible_code: visible_code.join("\n"),
+        location: text_range_to_byte_offsets(range),
+        span: line_index.span(range),
+    }
+}
+
+/// Reads a code block's info string the way rustdoc does: untagged blocks
+/// are Rust, and so are blocks tagged only with test attributes. Any other
+/// tag is the block's language, unless `rust` is given too.
+fn parse_code_block_info(info: &str) -> (String, Vec<String>) {
+    let mut language = None;
+    let mut attributes = Vec::new();
+    let mut is_rust = false;
+
+    for token in info
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|token| !token.is_empty())
+    {
+        match token {
+            "rust" => is_rust = true,
+            "ignore" | "no_run" | "should_panic" | "compile_fail" | "test_harness"
+            | "standalone_crate" => attributes.push(token.to_string()),
+            _ if token.starts_with("ignore-") || is_edition(token) => {
+                attributes.push(token.to_string())
+            }
+            _ => {
+                language.get_or_insert_with(|| token.to_string());
+            }
+        }
+    }
+
+    let language = match language {
+        Some(language) if !is_rust => language,
+        _ => "rust".to_string(),
+    };
+    (language, attributes)
+}
+
+fn is_edition(token: &str) -> bool {
+    token
+        .strip_prefix("edition")
+        .is_some_and(|year| !year.is_empty() && year.chars().all(|c| c.is_ascii_digit()))
+}
+
+/// A line of a Rust code block as rustdoc compiles it, and whether it is
+/// hidden from the rendered docs. `# ` hides a line and `##` escapes a
+/// leading `#`.
+fn hidden_line(line: &str) -> (String, bool) {
+    let trimmed = line.trim();
+    if trimmed.starts_with("##") {
+        (line.replacen("##", "#", 1), false)
+    } else if let Some(rest) = trimmed.strip_prefix("# ") {
+        (rest.to_string(), true)
+    } else if trimmed == "#" {
+        (String::new(), true)
+    } else {
+        (line.to_string(), false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ra_ap_syntax::{ast::HasModuleItem, Edition, SourceFile};
+
+    use super::*;
+
+    fn first_item(code: &str) -> ast::Item {
+        SourceFile::parse(code, Edition::CURRENT)
+            .tree()
+            .items()
+            .next()
+            .unwrap()
+    }
+
+    fn docs(code: &str) -> Option<String> {
+        extract_doc_comment(&first_item(code))
+    }
+
+    #[test]
+    fn strips_line_comment_markers_and_indentation() {
+        assert_eq!(
+            docs("/// Summary.\n///\n///     indented\nfn f() {}").as_deref(),
+            Some("Summary.\n\n    indented")
+        );
+        assert_eq!(docs("//// Not docs\nfn f() {}"), None);
+        assert_eq!(docs("// Not docs\nfn f() {}"), None);
+    }
+
+    #[test]
+    fn trims_leading_and_trailing_blank_lines() {
+        assert_eq!(
+            docs("///\n/// Text\n///\nfn f() {}").as_deref(),
+            Some("Text")
+        );
+    }
+
+    #[test]
+    fn beautifies_block_comments() {
+        assert_eq!(
+            docs("/**\n * Starred\n *\n * block\n */\nfn f() {}").as_deref(),
+            Some("Starred\n\nblock")
+        );
+        assert_eq!(
+            docs("/** Block doc\n * with stars\n */\nfn f() {}").as_deref(),
+            Some("Block doc\nwith stars")
+        );
+        assert_eq!(
+            docs("/**\n    Plain\n      indented\n*/\nfn f() {}").as_deref(),
+            Some("Plain\n  indented")
+        );
+        assert_eq!(
+            docs("/** One line */\nfn f() {}").as_deref(),
+            Some("One line")
+        );
+        assert_eq!(docs("/*** Not docs */\nfn f() {}"), None);
+    }
+
+    #[test]
+    fn unescapes_doc_attributes() {
+        assert_eq!(
+            docs("#[doc = \"Tab\\there\\nnext \\u{2764}\"]\nfn f() {}").as_deref(),
+            Some("Tab\there\nnext \u{2764}")
+        );
+        assert_eq!(
+            docs("#[doc = r#\"Raw \"quoted\" \\n\"#]\nfn f() {}").as_deref(),
+            Some("Raw \"quoted\" \\n")
+        );
+        assert_eq!(
+            docs("#[doc = include_str!(\"README.md\")]\nfn f() {}"),
+            None
+        );
+    }
+
+    #[test]
+    fn aligns_mixed_sugared_and_raw_docs() {
+        // Raw fragments count as one column further in than written
+        assert_eq!(
+            docs("/// Sugared\n#[doc = \"Raw\"]\n#[doc = \"  Indented\"]\nfn f() {}").as_deref(),
+            Some("Sugared\nRaw\n  Indented")
+        );
+        assert_eq!(
+            docs("#[doc = \" Raw\"]\n#[doc = \"Less\"]\nfn f() {}").as_deref(),
+            Some(" Raw\nLess")
+        );
+    }
+
+    #[test]
+    fn appends_inner_docs_of_inline_modules() {
+        let code = "/// Outer\nmod m {\n    //! Inner\n    #![doc = \"Attr\"]\n}";
+        assert_eq!(docs(code).as_deref(), Some("Outer\nInner\nAttr"));
+    }
+
+    #[test]
+    fn reads_inner_docs_of_files() {
+        let file = SourceFile::parse(
+            "//! File\n/*! Block */\n\n/// Item\nfn f() {}",
+            Edition::CURRENT,
+        )
+        .tree();
+        assert_eq!(
+            extract_inner_doc_comment(&file).as_deref(),
+            Some("File\nBlock")
+        );
+    }
+
+    #[test]
+    fn splits_summary_and_body() {
+        let sections = parse_doc_sections("First line\nwrapped.\n\nMore text.\n\nAnd more.");
+        assert_eq!(sections.summary.as_deref(), Some("First line wrapped."));
+        assert_eq!(sections.body.as_deref(), Some("More text.\n\nAnd more."));
+        assert_eq!(sections.examples, None);
+
+        let sections = parse_doc_sections("Summary only.");
+        assert_eq!(sections.summary.as_deref(), Some("Summary only."));
+        assert_eq!(sections.body, None);
+    }
+
+    #[test]
+    fn extracts_known_sections() {
+        let doc = "Summary.\n\n# Errors\n\nWhen it fails.\n\n# Panics\nNever.\n\n\
+                   ## Safety ##\nCareful.\n\n# Example\n\n```\nf();\n```";
+        let sections = parse_doc_sections(doc);
+        assert_eq!(sections.summary.as_deref(), Some("Summary."));
+        assert_eq!(sections.body, None);
+        assert_eq!(sections.errors.as_deref(), Some("When it fails."));
+        // `## Safety` is nested in `# Panics`, so it stays part of it
+        assert_eq!(
+            sections.panics.as_deref(),
+            Some("Never.\n\n## Safety ##\nCareful.")
+        );
+        assert_eq!(sections.safety, None);
+        assert_eq!(sections.examples.as_deref(), Some("```\nf();\n```"));
+    }
+
+    #[test]
+    fn ends_sections_at_headings_of_the_same_or_higher_level() {
+        let doc = "Summary.\n\n## Safety\n\nUnsafe.\n\n### Details\n\nNested.\n\n\
+                   # Notes\n\nIn the body.";
+        let sections = parse_doc_sections(doc);
+        assert_eq!(
+            sections.safety.as_deref(),
+            Some("Unsafe.\n\n### Details\n\nNested.")
+        );
+        assert_eq!(sections.body.as_deref(), Some("# Notes\n\nIn the body."));
+    }
+
+    #[test]
+    fn ignores_headings_in_code_blocks() {
+        let doc = "Summary.\n\n# Examples\n\n```\n# fn hidden() {}\n# Errors\n```\nAfter.";
+        let sections = parse_doc_sections(doc);
+        assert_eq!(
+            sections.examples.as_deref(),
+            Some("```\n# fn hidden() {}\n# Errors\n```\nAfter.")
+        );
+        assert_eq!(sections.errors, None);
+    }
+
+    #[test]
+    fn reads_atx_headings() {
+        assert_eq!(heading("# Examples"), Some((1, "Examples")));
+        assert_eq!(heading("  ### Safety ###"), Some((3, "Safety")));
+        assert_eq!(heading("#"), Some((1, "")));
+        assert_eq!(heading("#Examples"), None);
+        assert_eq!(heading("####### Seven"), None);
+    }
+
+    #[test]
+    fn starts_sections_without_a_summary() {
+        let sections = parse_doc_sections("# Panics\n\nAlways.");
+        assert_eq!(sections.summary, None);
+        assert_eq!(sections.panics.as_deref(), Some("Always."));
+    }
+
+    fn doctests(code: &str) -> Vec<Doctest> {
+        extract_doctests(&first_item(code), &LineIndex::new(code))
+    }
+
+    fn info(info: &str) -> (String, Vec<String>) {
+        parse_code_block_info(info)
+    }
+
+    #[test]
+    fn reads_code_block_info_strings() {
+        assert_eq!(info(""), ("rust".to_string(), vec![]));
+        assert_eq!(info("rust"), ("rust".to_string(), vec![]));
+        assert_eq!(
+            info("no_run, should_panic"),
+            (
+                "rust".to_string(),
+                vec!["no_run".into(), "should_panic".into()]
+            )
+        );
+        assert_eq!(
+            info("ignore-wasm32 edition2021"),
+            (
+                "rust".to_string(),
+                vec!["ignore-wasm32".into(), "edition2021".into()]
+            )
+        );
+        assert_eq!(info("text"), ("text".to_string(), vec![]));
+        assert_eq!(
+            info("toml,ignore"),
+            ("toml".to_string(), vec!["ignore".into()])
+        );
+        assert_eq!(info("sh rust"), ("rust".to_string(), vec![]));
+        assert_eq!(info("edition"), ("edition".to_string(), vec![]));
+    }
+
+    #[test]
+    fn hides_lines_the_way_rustdoc_does() {
+        assert_eq!(
+            hidden_line("# use std::io;"),
+            ("use std::io;".to_string(), true)
+        );
+        assert_eq!(
+            hidden_line("    # let x = 1;"),
+            ("let x = 1;".to_string(), true)
+        );
+        assert_eq!(hidden_line("#"), (String::new(), true));
+        assert_eq!(
+            hidden_line("#[derive(Debug)]"),
+            ("#[derive(Debug)]".to_string(), false)
+        );
+        assert_eq!(
+            hidden_line("## not hidden"),
+            ("# not hidden".to_string(), false)
+        );
+        assert_eq!(hidden_line("let x = 1;"), ("let x = 1;".to_string(), false));
+    }
+
+    #[test]
+    fn reads_fences() {
+        assert_eq!(opening_fence("```"), Some((0, "```", "")));
+        assert_eq!(
+            opening_fence("  ````rust,no_run "),
+            Some((2, "````", "rust,no_run"))
+        );
+        assert_eq!(opening_fence("~~~ text"), Some((0, "~~~", "text")));
+        assert_eq!(opening_fence("``not a fence"), None);
+        assert_eq!(opening_fence("```a`b"), None);
+        assert!(is_closing_fence("````", "```"));
+        assert!(!is_closing_fence("```", "````"));
+        assert!(!is_closing_fence("~~~", "```"));
+        assert!(!is_closing_fence("``` rust", "```"));
+    }
+
+    #[test]
+    fn extracts_doctests() {
+        let code = "/// Summary.\n///\n/// ```\n/// # use m::f;\n/// f();\n/// ```\n///\n\
+                    /// ```text\n/// # a heading\n/// ```\nfn f() {}";
+        let tests = doctests(code);
+        assert_eq!(tests.len(), 2);
+
+        assert_eq!(tests[0].language, "rust");
+        assert_eq!(tests[0].code, "use m::f;\nf();");
+        assert_eq!(tests[0].visible_code, "f();");
+        let start = code.find("/// ```").unwrap() as u32;
+        let end = code.find("///\n/// ```text").unwrap() as u32 - 1;
+        assert_eq!(tests[0].location, [start, end]);
+        assert_eq!(tests[0].span.start.line, 3);
+        assert_eq!(tests[0].span.end.line, 6);
+
+        assert_eq!(tests[1].language, "text");
+        assert_eq!(tests[1].code, "# a heading");
+        assert_eq!(tests[1].visible_code, "# a heading");
+    }
+
+    #[test]
+    fn keeps_indented_and_unclosed_blocks() {
+        let tests =
+            doctests("/// - item:\n///   ```\n///   let x = 1;\n///     nested();\nfn f() {}");
+        assert_eq!(tests.len(), 1);
+        assert_eq!(tests[0].code, "let x = 1;\n  nested();");
+    }
+
+    #[test]
+    fn ignores_fences_that_dont_close_the_block() {
+        let tests = doctests("/// ````\n/// ```\n/// inner\n/// ```\n/// ````\nfn f() {}");
+        assert_eq!(tests.len(), 1);
+        assert_eq!(tests[0].code, "```\ninner\n```");
+    }
+}
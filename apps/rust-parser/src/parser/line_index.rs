@@ -0,0 +1,146 @@
+use ra_ap_syntax::{TextRange, TextSize};
+
+use super::{Position, SourceSpan};
+
+/// Maps byte offsets in a source file to line/column positions.
+///
+/// Built once per file so that every location we emit can be converted
+/// without rescanning the source from the start.
+pub struct LineIndex<'a> {
+    text: &'a str,
+    line_starts: Vec<u32>, // byte offset of the first character of each line
+    line_starts_utf16: Vec<u32>, // the same offsets, counted in UTF-16 code units
+}
+
+impl<'a> LineIndex<'a> {
+    pub fn new(text: &'a str) -> Self {
+        let mut line_starts = vec![0];
+        let mut line_starts_utf16 = vec![0];
+        let mut utf16_offset = 0;
+        for (offset, c) in text.char_indices() {
+            utf16_offset += c.len_utf16() as u32;
+            if c == '\n' {
+                line_starts.push(offset as u32 + 1);
+                line_starts_utf16.push(utf16_offset);
+            }
+        }
+
+        LineIndex {
+            text,
+            line_starts,
+            line_starts_utf16,
+        }
+    }
+
+    pub fn position(&self, offset: TextSize) -> Position {
+        let offset = u32::from(offset);
+        let line = match self.line_starts.binary_search(&offset) {
+            Ok(line) => line,
+            Err(next_line) => next_line - 1,
+        };
+        let line_start = self.line_starts[line] as usize;
+        let line_prefix = self.text.get(line_start..offset as usize).unwrap_or("");
+        let utf16_column = line_prefix
+            .chars()
+            .map(|c| c.len_utf16() as u32)
+            .sum::<u32>();
+
+        Position {
+            line: line as u32 + 1,
+            column: line_prefix.chars().count() as u32 + 1,
+            utf16_column: utf16_column + 1,
+            utf16_offset: self.line_starts_utf16[line] + utf16_column,
+        }
+    }
+
+    pub fn span(&self, range: TextRange) -> SourceSpan {
+        SourceSpan {
+            start: self.position(range.start()),
+            end: self.position(range.end()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `(line, column, utf16_column, utf16_offset)` of a byte offset.
+    fn position(text: &str, offset: usize) -> (u32, u32, u32, u32) {
+        let position = LineIndex::new(text).position(TextSize::from(offset as u32));
+        (
+            position.line,
+            position.column,
+            position.utf16_column,
+            position.utf16_offset,
+        )
+    }
+
+    #[test]
+    fn counts_lines_and_columns_from_one() {
+        let text = "fn a() {}\nfn b() {}\n";
+        assert_eq!(position(text, 0), (1, 1, 1, 0));
+        assert_eq!(position(text, 3), (1, 4, 4, 3));
+        // The newline itself belongs to the line it ends
+        assert_eq!(position(text, 9), (1, 10, 10, 9));
+    }
+
+    #[test]
+    fn starts_a_new_line_after_each_newline() {
+        let text = "a\nbc\n\nd";
+        assert_eq!(position(text, 2), (2, 1, 1, 2));
+        assert_eq!(position(text, 5), (3, 1, 1, 5));
+        assert_eq!(position(text, 6), (4, 1, 1, 6));
+    }
+
+    #[test]
+    fn counts_multi_byte_characters_once() {
+        // `é` is two bytes and one UTF-16 unit
+        let text = "é = 1;\nlet ü = 2;";
+        assert_eq!(position(text, "é".len()), (1, 2, 2, 1));
+        let offset = text.find('=').unwrap();
+        assert_eq!(position(text, offset), (1, 3, 3, 2));
+        let offset = text.rfind('=').unwrap();
+        assert_eq!(position(text, offset), (2, 7, 7, 13));
+    }
+
+    #[test]
+    fn counts_astral_characters_as_surrogate_pairs() {
+        // `🦀` is four bytes, one character and two UTF-16 units
+        let text = "\"🦀\" x\n🦀y";
+        let offset = text.find('x').unwrap();
+        assert_eq!(position(text, offset), (1, 5, 6, 5));
+        let offset = text.find('y').unwrap();
+        assert_eq!(position(text, offset), (2, 2, 3, 9));
+    }
+
+    #[test]
+    fn keeps_carriage_returns_on_their_line() {
+        let text = "a\r\nb\r\n";
+        assert_eq!(position(text, 1), (1, 2, 2, 1));
+        assert_eq!(position(text, 3), (2, 1, 1, 3));
+        assert_eq!(position(text, 4), (2, 2, 2, 4));
+    }
+
+    #[test]
+    fn maps_the_end_of_the_file() {
+        let text = "ab\ncd";
+        assert_eq!(position(text, text.len()), (2, 3, 3, 5));
+        let text = "ab\n";
+        assert_eq!(position(text, text.len()), (2, 1, 1, 3));
+        assert_eq!(position("", 0), (1, 1, 1, 0));
+    }
+
+    #[test]
+    fn spans_cover_both_ends() {
+        let text = "x\ny🦀z";
+        let start = TextSize::from(2);
+        let end = TextSize::from(text.len() as u32);
+        let span = LineIndex::new(text).span(TextRange::new(start, end));
+        assert_eq!((span.start.line, span.start.column), (2, 1));
+        assert_eq!(
+            (span.end.line, span.end.column, span.end.utf16_column),
+            (2, 4, 5)
+        );
+    }
+}
@@ -0,0 +1,94 @@
+use super::Edition;
+
+/// The edition a Cargo.toml declares for its package.
+///
+/// Cargo uses 2015 when `edition` is left out. Manifests without a
+/// `[package]` and editions inherited from the workspace give `None`, since
+/// they can't be resolved from this file alone.
+///
+/// Only the keys we need are read, so this is a line scan rather than a
+/// full TOML parser.
+pub fn manifest_edition(manifest: &str) -> Option<Edition> {
+    let mut table = String::new();
+    let mut has_package = false;
+
+    for line in manifest.lines() {
+        let line = line.split('#').next().unwrap_or_default().trim();
+        if line.starts_with('[') {
+            table = line
+                .trim_matches(|c| c == '[' || c == ']')
+                .trim()
+                .to_string();
+            has_package |= table == "package";
+            continue;
+        }
+
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let key = match (table.as_str(), key.trim()) {
+            ("package", key) => key.to_string(),
+            ("", key) => match key.strip_prefix("package.") {
+                Some(key) => {
+                    has_package = true;
+                    key.to_string()
+                }
+                None => continue,
+            },
+            _ => continue,
+        };
+
+        match key.as_str() {
+            "edition" => return Edition::from_year(value.trim().trim_matches(['"', '\''])),
+            "edition.workspace" => return None,
+            _ => {}
+        }
+    }
+
+    has_package.then_some(Edition::Edition2015)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_the_package_edition() {
+        let manifest = "[package]\nname = \"foo\"\nedition = \"2021\"\n";
+        assert_eq!(manifest_edition(manifest), Some(Edition::Edition2021));
+        let manifest = "[ package ]\nedition = '2018' # old\n";
+        assert_eq!(manifest_edition(manifest), Some(Edition::Edition2018));
+        let manifest = "package.name = \"foo\"\npackage.edition = \"2024\"\n";
+        assert_eq!(manifest_edition(manifest), Some(Edition::Edition2024));
+    }
+
+    #[test]
+    fn defaults_to_2015() {
+        let manifest = "[package]\nname = \"foo\"\n\n[dependencies]\nedition = \"1\"\n";
+        assert_eq!(manifest_edition(manifest), Some(Edition::Edition2015));
+        let manifest = "[package]\n# edition = \"2021\"\nname = \"foo\"\n";
+        assert_eq!(manifest_edition(manifest), Some(Edition::Edition2015));
+    }
+
+    #[test]
+    fn ignores_other_tables() {
+        let manifest = "[workspace.package]\nedition = \"2021\"\n";
+        assert_eq!(manifest_edition(manifest), None);
+        let manifest = "[package]\nname = \"foo\"\n\n[[bin]]\nedition = \"2021\"\n";
+        assert_eq!(manifest_edition(manifest), Some(Edition::Edition2015));
+    }
+
+    #[test]
+    fn leaves_workspace_editions_unresolved() {
+        let manifest = "[package]\nedition.workspace = true\n";
+        assert_eq!(manifest_edition(manifest), None);
+        let manifest = "[package]\nedition = { workspace = true }\n";
+        assert_eq!(manifest_edition(manifest), None);
+    }
+
+    #[test]
+    fn rejects_unknown_editions() {
+        assert_eq!(manifest_edition("[package]\nedition = \"2027\"\n"), None);
+        assert_eq!(manifest_edition(""), None);
+    }
+}
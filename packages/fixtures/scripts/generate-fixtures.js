@@ -31,14 +31,19 @@ function findRustFiles(dir) {
   return results
 }
 
-function generateParsedOutput(rustFile, outputFile) {
-  console.log(`Parsing ${rustFile} -> ${outputFile}`)
+function generateParsedOutput(sourceDir, relativePath, outputFile) {
+  console.log(`Parsing ${relativePath} -> ${outputFile}`)
 
   try {
-    const output = execSync(`"${RUST_PARSER_BIN}" "${rustFile}"`, {
-      encoding: 'utf8',
-      cwd: path.join(__dirname, '..', '..', '..'),
-    })
+    // Parse from the crate root so that module paths come out relative to
+    // it (src/foo.rs), as they do for the workflow
+    const output = execSync(
+      `"${RUST_PARSER_BIN}" "${relativePath}" --manifest-path Cargo.toml`,
+      {
+        encoding: 'utf8',
+        cwd: sourceDir,
+      },
+    )
 
     // Ensure output directory exists
     fs.mkdirSync(path.dirname(outputFile), { recursive: true })
@@ -47,7 +52,7 @@ function generateParsedOutput(rustFile, outputFile) {
     fs.writeFileSync(outputFile, output)
     console.log(`  ✓ Generated ${outputFile}`)
   } catch (error) {
-    console.error(`  ✗ Failed to parse ${rustFile}:`, error.message)
+    console.error(`  ✗ Failed to parse ${relativePath}:`, error.message)
   }
 }
 
@@ -84,7 +89,7 @@ function processCrate(crateName) {
     // Create corresponding output path with .json extension
     const outputFile = path.join(outputDir, relativePath + '.json')
 
-    generateParsedOutput(rustFile, outputFile)
+    generateParsedOutput(sourceDir, relativePath, outputFile)
   }
 
   console.log(`Finished processing ${crateName}\n`)
@@ -3,17 +3,53 @@ import type { FileInfo as ParsedFileInfo } from './rust_parser_generated/FileInf
 
 // Direct re-exports of generated types
 export type { AdtDetails } from './rust_parser_generated/AdtDetails'
+export type { AssocConstInfo } from './rust_parser_generated/AssocConstInfo'
+export type { AssocTypeInfo } from './rust_parser_generated/AssocTypeInfo'
+export type { AttributeInfo } from './rust_parser_generated/AttributeInfo'
+export type { Deprecation } from './rust_parser_generated/Deprecation'
+export type { DocSections } from './rust_parser_generated/DocSections'
+export type { Doctest } from './rust_parser_generated/Doctest'
+export type { Edition } from './rust_parser_generated/Edition'
+export type { ExternBlockDetails } from './rust_parser_generated/ExternBlockDetails'
+export type { ExternCrateDetails } from './rust_parser_generated/ExternCrateDetails'
+export type { FieldInfo } from './rust_parser_generated/FieldInfo'
+export type { FieldListKind } from './rust_parser_generated/FieldListKind'
+export type { ForeignItemInfo } from './rust_parser_generated/ForeignItemInfo'
+export type { ForeignItemKind } from './rust_parser_generated/ForeignItemKind'
 export type { FunctionDetails } from './rust_parser_generated/FunctionDetails'
+export type { FunctionParam } from './rust_parser_generated/FunctionParam'
+export type { GenericParam } from './rust_parser_generated/GenericParam'
+export type { GenericParamKind } from './rust_parser_generated/GenericParamKind'
+export type { Generics } from './rust_parser_generated/Generics'
+export type { ImplDetails } from './rust_parser_generated/ImplDetails'
+export type { ImplOrigin } from './rust_parser_generated/ImplOrigin'
+export type { ImportInfo } from './rust_parser_generated/ImportInfo'
+export type { InlineKind } from './rust_parser_generated/InlineKind'
 export type { ItemDetails } from './rust_parser_generated/ItemDetails'
+export type { KnownAttributes } from './rust_parser_generated/KnownAttributes'
+export type { LinkInfo } from './rust_parser_generated/LinkInfo'
+export type { MacroDetails } from './rust_parser_generated/MacroDetails'
+export type { MacroKind } from './rust_parser_generated/MacroKind'
+export type { MacroRule } from './rust_parser_generated/MacroRule'
 export type { ModuleDetails } from './rust_parser_generated/ModuleDetails'
 export type { ModuleInfo } from './rust_parser_generated/ModuleInfo'
 export type { ModuleReference } from './rust_parser_generated/ModuleReference'
 export type { OtherDetails } from './rust_parser_generated/OtherDetails'
 export type { ParseError } from './rust_parser_generated/ParseError'
+export type { ParseErrorCode } from './rust_parser_generated/ParseErrorCode'
 export type { ParseRequest } from './rust_parser_generated/ParseRequest'
 export type { ParseResponse } from './rust_parser_generated/ParseResponse'
+export type { Position } from './rust_parser_generated/Position'
+export type { Receiver } from './rust_parser_generated/Receiver'
+export type { SourceSpan } from './rust_parser_generated/SourceSpan'
 export type { TraitDetails } from './rust_parser_generated/TraitDetails'
+export type { TraitImplInfo } from './rust_parser_generated/TraitImplInfo'
 export type { TraitMethodInfo } from './rust_parser_generated/TraitMethodInfo'
+export type { TypeAliasDetails } from './rust_parser_generated/TypeAliasDetails'
+export type { UseDetails } from './rust_parser_generated/UseDetails'
+export type { VariantInfo } from './rust_parser_generated/VariantInfo'
+export type { Visibility } from './rust_parser_generated/Visibility'
+export type { WherePredicate } from './rust_parser_generated/WherePredicate'
 
 // Keep the ItemType enum as it's useful
 export enum ItemType {
@@ -80,6 +116,7 @@ export interface CrateWithData {
   name: string
   version: string
   files: Map<string, string>
+  manifest?: string // Contents of the crate's Cargo.toml
 }
 
 // Extended type to include AI-generated summary
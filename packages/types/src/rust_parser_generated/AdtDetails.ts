@@ -1,4 +1,9 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { FieldInfo } from "./FieldInfo";
+import type { FieldListKind } from "./FieldListKind";
+import type { Generics } from "./Generics";
 import type { ItemInfo } from "./ItemInfo";
+import type { TraitImplInfo } from "./TraitImplInfo";
+import type { VariantInfo } from "./VariantInfo";
 
-export type AdtDetails = { adtType: string, methods: Array<ItemInfo>, };
+export type AdtDetails = { adtType: string, generics: Generics, fieldKind: FieldListKind | null, fields: Array<FieldInfo>, variants: Array<VariantInfo>, traitImpls: Array<TraitImplInfo>, methods: Array<ItemInfo>, };
@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SourceSpan } from "./SourceSpan";
+
+export type AssocConstInfo = { name: string, type: string | null, default: string | null, docComment: string | null, location: [number, number], span: SourceSpan, };
@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type DocSections = { summary: string | null, body: string | null, examples: string | null, errors: string | null, panics: string | null, safety: string | null, };
@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SourceSpan } from "./SourceSpan";
+
+export type Doctest = { language: string, attributes: Array<string>, code: string, visibleCode: string, location: [number, number], span: SourceSpan, };
@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * The Rust edition a file is parsed with.
+ *
+ * Editions change what is a keyword (`async`, `dyn`, `gen`, ...), so
+ * older crates can misparse under a newer one.
+ */
+export type Edition = "2015" | "2018" | "2021" | "2024";
@@ -0,0 +1,5 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ForeignItemInfo } from "./ForeignItemInfo";
+import type { LinkInfo } from "./LinkInfo";
+
+export type ExternBlockDetails = { abi: string, isUnsafe: boolean, links: Array<LinkInfo>, items: Array<ForeignItemInfo>, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AttributeInfo } from "./AttributeInfo";
+import type { SourceSpan } from "./SourceSpan";
+import type { Visibility } from "./Visibility";
+
+export type FieldInfo = { name: string, type: string, visibility: Visibility, docComment: string | null, attributes: Array<AttributeInfo>, location: [number, number], span: SourceSpan, };
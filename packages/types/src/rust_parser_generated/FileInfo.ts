@@ -2,4 +2,4 @@
 import type { ItemInfo } from "./ItemInfo";
 import type { ModuleReference } from "./ModuleReference";
 
-export type FileInfo = { items: Array<ItemInfo>, moduleReferences: Array<ModuleReference>, };
+export type FileInfo = { docComment: string | null, items: Array<ItemInfo>, moduleReferences: Array<ModuleReference>, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ForeignItemKind } from "./ForeignItemKind";
+import type { SourceSpan } from "./SourceSpan";
+import type { Visibility } from "./Visibility";
+
+export type ForeignItemInfo = { name: string, kind: ForeignItemKind, signature: string, visibility: Visibility, linkName: string | null, docComment: string | null, location: [number, number], span: SourceSpan, };
@@ -1,3 +1,7 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { FunctionParam } from "./FunctionParam";
+import type { Generics } from "./Generics";
+import type { ImplOrigin } from "./ImplOrigin";
+import type { Receiver } from "./Receiver";
 
-export type FunctionDetails = { signature: string, };
+export type FunctionDetails = { signature: string, isAsync: boolean, isConst: boolean, isUnsafe: boolean, abi: string | null, receiver: Receiver | null, params: Array<FunctionParam>, returnType: string | null, generics: Generics, implOrigin: ImplOrigin | null, };
@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { GenericParamKind } from "./GenericParamKind";
+
+export type GenericParam = { name: string, kind: GenericParamKind, bounds: Array<string>, type: string | null, default: string | null, };
@@ -0,0 +1,5 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { GenericParam } from "./GenericParam";
+import type { WherePredicate } from "./WherePredicate";
+
+export type Generics = { params: Array<GenericParam>, wherePredicates: Array<WherePredicate>, };
@@ -1,8 +1,14 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 import type { AdtDetails } from "./AdtDetails";
+import type { ExternBlockDetails } from "./ExternBlockDetails";
+import type { ExternCrateDetails } from "./ExternCrateDetails";
 import type { FunctionDetails } from "./FunctionDetails";
+import type { ImplDetails } from "./ImplDetails";
+import type { MacroDetails } from "./MacroDetails";
 import type { ModuleDetails } from "./ModuleDetails";
 import type { OtherDetails } from "./OtherDetails";
 import type { TraitDetails } from "./TraitDetails";
+import type { TypeAliasDetails } from "./TypeAliasDetails";
+import type { UseDetails } from "./UseDetails";
 
-export type ItemDetails = { "function": FunctionDetails } | { "adt": AdtDetails } | { "trait": TraitDetails } | { "module": ModuleDetails } | { "other": OtherDetails };
+export type ItemDetails = { "function": FunctionDetails } | { "adt": AdtDetails } | { "trait": TraitDetails } | { "impl": ImplDetails } | { "typeAlias": TypeAliasDetails } | { "macro": MacroDetails } | { "externBlock": ExternBlockDetails } | { "externCrate": ExternCrateDetails } | { "use": UseDetails } | { "module": ModuleDetails } | { "other": OtherDetails };
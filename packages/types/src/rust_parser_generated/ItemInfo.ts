@@ -1,4 +1,10 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AttributeInfo } from "./AttributeInfo";
+import type { DocSections } from "./DocSections";
+import type { Doctest } from "./Doctest";
 import type { ItemDetails } from "./ItemDetails";
+import type { KnownAttributes } from "./KnownAttributes";
+import type { SourceSpan } from "./SourceSpan";
+import type { Visibility } from "./Visibility";
 
-export type ItemInfo = { name: string, fullCode: string, docComment: string | null, visibility: string, location: [number, number], details: ItemDetails, };
+export type ItemInfo = { name: string, fullCode: string, docComment: string | null, docSections: DocSections | null, doctests: Array<Doctest>, visibility: Visibility, isPublicApi: boolean, attributes: Array<AttributeInfo>, knownAttributes: KnownAttributes, cfg: string | null, isCfgActive: boolean, location: [number, number], span: SourceSpan, details: ItemDetails, };
@@ -0,0 +1,5 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Deprecation } from "./Deprecation";
+import type { InlineKind } from "./InlineKind";
+
+export type KnownAttributes = { derives: Array<string>, isMustUse: boolean, isNonExhaustive: boolean, inline: InlineKind | null, repr: Array<string>, deprecated: Deprecation | null, isDocHidden: boolean, };
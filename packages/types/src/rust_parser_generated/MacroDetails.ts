@@ -0,0 +1,5 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { MacroKind } from "./MacroKind";
+import type { MacroRule } from "./MacroRule";
+
+export type MacroDetails = { kind: MacroKind, isExported: boolean, rules: Array<MacroRule>, helperAttributes: Array<string>, functionName: string | null, };
@@ -1,5 +1,6 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 import type { ItemInfo } from "./ItemInfo";
 import type { ModuleReference } from "./ModuleReference";
+import type { SourceSpan } from "./SourceSpan";
 
-export type ModuleInfo = { name: string, items: Array<ItemInfo>, inlineModules: Array<ModuleInfo>, moduleReferences: Array<ModuleReference>, location: [number, number], };
+export type ModuleInfo = { name: string, items: Array<ItemInfo>, inlineModules: Array<ModuleInfo>, moduleReferences: Array<ModuleReference>, location: [number, number], span: SourceSpan, };
@@ -1,3 +1,5 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SourceSpan } from "./SourceSpan";
+import type { Visibility } from "./Visibility";
 
-export type ModuleReference = { name: string, visibility: string, expectedPaths: Array<string>, location: [number, number], };
+export type ModuleReference = { name: string, visibility: Visibility, isPublicApi: boolean, expectedPaths: Array<string>, pathAttribute: string | null, cfg: string | null, isCfgActive: boolean, location: [number, number], span: SourceSpan, };
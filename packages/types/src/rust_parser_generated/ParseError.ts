@@ -1,3 +1,5 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ParseErrorCode } from "./ParseErrorCode";
+import type { SourceSpan } from "./SourceSpan";
 
-export type ParseError = { message: string, severity: string, location: [number, number] | null, };
+export type ParseError = { message: string, code: ParseErrorCode, severity: string, location: [number, number] | null, span: SourceSpan | null, };
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Machine-readable category for a syntax error.
+ *
+ * `ra_ap_syntax` only gives us a message, so the code is derived from it.
+ */
+export type ParseErrorCode = "expected" | "unmatchedDelimiter" | "unterminatedLiteral" | "invalidLiteral" | "invalidVisibility" | "other";
@@ -1,3 +1,4 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Edition } from "./Edition";
 
-export type ParseRequest = { code: string, filePath: string | null, includePrivate: boolean, };
+export type ParseRequest = { code: string, filePath: string | null, includePrivate: boolean, cfg: Array<string> | null, edition: Edition | null, manifest: string | null, };
@@ -1,4 +1,7 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AssocConstInfo } from "./AssocConstInfo";
+import type { AssocTypeInfo } from "./AssocTypeInfo";
+import type { Generics } from "./Generics";
 import type { TraitMethodInfo } from "./TraitMethodInfo";
 
-export type TraitDetails = { methods: Array<TraitMethodInfo>, };
+export type TraitDetails = { isUnsafe: boolean, isAuto: boolean, generics: Generics, supertraits: Array<string>, associatedTypes: Array<AssocTypeInfo>, associatedConsts: Array<AssocConstInfo>, methods: Array<TraitMethodInfo>, };
@@ -0,0 +1,5 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Generics } from "./Generics";
+import type { SourceSpan } from "./SourceSpan";
+
+export type TraitImplInfo = { traitPath: string, generics: Generics, isNegative: boolean, isUnsafe: boolean, location: [number, number], span: SourceSpan, };
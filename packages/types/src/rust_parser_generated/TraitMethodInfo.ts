@@ -1,3 +1,4 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SourceSpan } from "./SourceSpan";
 
-export type TraitMethodInfo = { name: string, signature: string, docComment: string | null, location: [number, number], };
+export type TraitMethodInfo = { name: string, signature: string, isRequired: boolean, docComment: string | null, location: [number, number], span: SourceSpan, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AttributeInfo } from "./AttributeInfo";
+import type { FieldInfo } from "./FieldInfo";
+import type { FieldListKind } from "./FieldListKind";
+import type { SourceSpan } from "./SourceSpan";
+
+export type VariantInfo = { name: string, kind: FieldListKind, fields: Array<FieldInfo>, discriminant: string | null, docComment: string | null, attributes: Array<AttributeInfo>, location: [number, number], span: SourceSpan, };